@@ -18,6 +18,7 @@ pub trait Interpolation {
     /// Note that we're using `f64` instead of `f32` like in the rest of the library, because it
     /// is common to start an animation at `UNIX_EPOCH` which is far away enough to cause precision
     /// issues.
+    #[allow(clippy::wrong_self_convention)]
     fn from_progress(&self, anim_progress: f64) -> f64;
 
     /// Takes an instance representing the current point in time, an instant representing the
@@ -70,6 +71,7 @@ pub struct Linear;
 
 impl Interpolation for Linear {
     #[inline]
+    #[allow(clippy::manual_clamp)]
     fn from_progress(&self, anim_progress: f64) -> f64 {
         if anim_progress >= 1.0 {
             1.0
@@ -96,7 +98,7 @@ impl EaseOut {
     #[inline]
     pub fn new(factor: f64) -> EaseOut {
         EaseOut {
-            factor: factor,
+            factor,
         }
     }
 }
@@ -127,7 +129,7 @@ impl<I> Reversed<I> where I: Interpolation {
     #[inline]
     pub fn new(inner: I) -> Reversed<I> {
         Reversed {
-            inner: inner,
+            inner,
         }
     }
 }
@@ -150,7 +152,7 @@ impl<I> Repeated<I> where I: Interpolation {
     #[inline]
     pub fn new(inner: I) -> Repeated<I> {
         Repeated {
-            inner: inner,
+            inner,
         }
     }
 }
@@ -176,7 +178,7 @@ impl<I> AlternateRepeated<I> where I: Interpolation {
     #[inline]
     pub fn new(inner: I) -> AlternateRepeated<I> {
         AlternateRepeated {
-            inner: inner,
+            inner,
         }
     }
 }
@@ -6,7 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 /// Represents an identifier of a widget. See the documentation of `DrawContext`.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WidgetId(usize);
 
 impl From<usize> for WidgetId {
@@ -0,0 +1,275 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::time::SystemTime;
+
+/// A key of the keyboard that widgets can react to.
+///
+/// Only the keys that are used to interact with the widgets are listed here. Text input is not
+/// handled by this library.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// The escape key. Usually closes popups and dialogs.
+    Escape,
+    /// The enter or return key.
+    Enter,
+    /// The space bar.
+    Space,
+    /// The tab key.
+    Tab,
+    /// The up arrow.
+    Up,
+    /// The down arrow.
+    Down,
+    /// The left arrow.
+    Left,
+    /// The right arrow.
+    Right,
+    /// The home key.
+    Home,
+    /// The end key.
+    End,
+    /// The page up key.
+    PageUp,
+    /// The page down key.
+    PageDown,
+}
+
+/// A button of the mouse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// The left button, or the touch of a finger. Also called the primary button.
+    Left,
+    /// The right button. Also called the secondary button.
+    Right,
+    /// The middle button, usually the wheel.
+    Middle,
+}
+
+/// State of the input devices during a frame. See `SharedDrawContext::draw_with_input`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    /// Position of the cursor, if any, in OpenGL viewport coordinates. In other words,
+    /// `[-1.0, -1.0]` corresponds to the bottom-left hand corner of the screen, and `[1.0, 1.0]`
+    /// to the top-right hand corner.
+    pub cursor: Option<[f32; 2]>,
+
+    /// State of the left button.
+    pub left: ButtonState,
+    /// State of the right button.
+    pub right: ButtonState,
+    /// State of the middle button.
+    pub middle: ButtonState,
+
+    /// Distance scrolled with the mouse wheel since the previous frame, horizontally and
+    /// vertically, in lines. Positive values scroll towards the right and the bottom.
+    pub wheel: [f32; 2],
+
+    /// Modifier keys that are held.
+    pub modifiers: Modifiers,
+
+    /// Keys that have been pressed since the previous frame. See `DrawContext::key_was_pressed`.
+    pub keys: Vec<Key>,
+
+    /// Points of contact with a touch screen. See `DrawContext::captured_touches`.
+    ///
+    /// Platforms usually also report the first touch as the cursor, so that the widgets that only
+    /// handle the cursor keep working on touch screens.
+    pub touches: Vec<Touch>,
+
+    /// Directional inputs that have been pressed since the previous frame, for example with the
+    /// directional pad of a gamepad. See `DrawContext::focusable`.
+    pub navigation: Vec<Navigation>,
+
+    /// Time of the frame.
+    pub time: SystemTime,
+}
+
+impl Input {
+    /// Builds an `Input` with the given cursor position, no button down, no wheel movement, no
+    /// modifier, no key, no touch, no navigation, and the current time.
+    #[inline]
+    pub fn new(cursor: Option<[f32; 2]>) -> Input {
+        Input {
+            cursor,
+            left: ButtonState::default(),
+            right: ButtonState::default(),
+            middle: ButtonState::default(),
+            wheel: [0.0, 0.0],
+            modifiers: Modifiers::default(),
+            keys: Vec::new(),
+            touches: Vec::new(),
+            navigation: Vec::new(),
+            time: SystemTime::now(),
+        }
+    }
+
+    /// Returns the state of the given button.
+    #[inline]
+    pub fn button(&self, button: MouseButton) -> &ButtonState {
+        match button {
+            MouseButton::Left => &self.left,
+            MouseButton::Right => &self.right,
+            MouseButton::Middle => &self.middle,
+        }
+    }
+}
+
+/// State of a mouse button during a frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ButtonState {
+    /// True if the button is down.
+    pub down: bool,
+    /// True if the button went from up to down since the previous frame.
+    pub pressed: bool,
+    /// True if the button went from down to up since the previous frame.
+    pub released: bool,
+}
+
+/// Modifier keys of the keyboard.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    /// One of the shift keys is held.
+    pub shift: bool,
+    /// One of the control keys is held.
+    pub ctrl: bool,
+    /// One of the alt keys is held.
+    pub alt: bool,
+    /// The logo key is held. That's the Windows key on PC and the command key on Mac.
+    pub logo: bool,
+}
+
+/// An input that moves the focus between widgets or interacts with the focused widget.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Navigation {
+    /// Moves the focus to the nearest widget above the focused one.
+    Up,
+    /// Moves the focus to the nearest widget below the focused one.
+    Down,
+    /// Moves the focus to the nearest widget at the left of the focused one.
+    Left,
+    /// Moves the focus to the nearest widget at the right of the focused one.
+    Right,
+    /// Activates the focused widget, like a click.
+    Accept,
+    /// Goes back, for example by closing a popup or a dialog.
+    Back,
+}
+
+/// A point of contact with a touch screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Touch {
+    /// Identifier of the touch. Must be the same during all the frames between the start and the
+    /// end of the touch.
+    pub id: u64,
+    /// Position of the touch, in the same coordinates as `Input::cursor`.
+    pub position: [f32; 2],
+    /// Step of the touch during this frame.
+    pub phase: TouchPhase,
+}
+
+/// Step of a touch during a frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    /// The finger touched the screen since the previous frame.
+    Started,
+    /// The finger is still on the screen. It may or may not have moved.
+    Moved,
+    /// The finger left the screen since the previous frame.
+    Ended,
+    /// The touch has been interrupted by the platform.
+    Cancelled,
+}
+
+/// A touch that started over a widget and that is captured by this widget until it ends. See
+/// `DrawContext::captured_touches`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CapturedTouch {
+    /// Identifier of the touch.
+    pub id: u64,
+    /// Position of the touch in the viewport, in logical pixels.
+    pub position: [f32; 2],
+    /// Position of the touch during the previous frame, in logical pixels. Same as `position`
+    /// during the frame when the touch started.
+    pub previous_position: [f32; 2],
+    /// Step of the touch during this frame.
+    pub phase: TouchPhase,
+}
+
+/// Movement of two fingers on a touch screen, for example to zoom and move a map.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Gesture {
+    /// Position of the middle of the two touches, in logical pixels.
+    pub center: [f32; 2],
+    /// Movement of the middle of the two touches since the previous frame, in logical pixels.
+    pub pan: [f32; 2],
+    /// Distance between the two touches divided by their distance during the previous frame.
+    /// Greater than `1.0` when the fingers move apart.
+    pub zoom: f32,
+}
+
+impl Gesture {
+    /// Builds the gesture made by the first two touches that are still on the screen, or returns
+    /// `None` if there are less than two of them.
+    pub fn from_touches(touches: &[CapturedTouch]) -> Option<Gesture> {
+        let mut touches = touches.iter().filter(|t| {
+            t.phase == TouchPhase::Started || t.phase == TouchPhase::Moved
+        });
+
+        let (a, b) = match (touches.next(), touches.next()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return None,
+        };
+
+        let middle = |p: [f32; 2], q: [f32; 2]| [(p[0] + q[0]) * 0.5, (p[1] + q[1]) * 0.5];
+        let distance = |p: [f32; 2], q: [f32; 2]| {
+            ((p[0] - q[0]) * (p[0] - q[0]) + (p[1] - q[1]) * (p[1] - q[1])).sqrt()
+        };
+
+        let center = middle(a.position, b.position);
+        let previous_center = middle(a.previous_position, b.previous_position);
+        let previous_distance = distance(a.previous_position, b.previous_position);
+
+        Some(Gesture {
+            center,
+            pan: [center[0] - previous_center[0], center[1] - previous_center[1]],
+            zoom: if previous_distance > 0.0 {
+                distance(a.position, b.position) / previous_distance
+            } else {
+                1.0
+            },
+        })
+    }
+}
+
+/// Shape of the mouse cursor. See `DrawContext::request_cursor_icon`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CursorIcon {
+    /// The usual arrow.
+    Arrow,
+    /// A pointing hand, usually over something clickable.
+    Hand,
+    /// A vertical bar, usually over some text that can be selected or edited.
+    IBeam,
+    /// A horizontal double arrow, usually over a border that can be dragged left and right.
+    ResizeHorizontal,
+    /// A vertical double arrow, usually over a border that can be dragged up and down.
+    ResizeVertical,
+    /// Four arrows, usually over something that can be moved in any direction.
+    Move,
+    /// A closed hand, while something is being dragged.
+    Grabbing,
+    /// A circle with a line through it, usually over something that is disabled.
+    NotAllowed,
+}
+
+impl Default for CursorIcon {
+    #[inline]
+    fn default() -> CursorIcon {
+        CursorIcon::Arrow
+    }
+}
@@ -5,60 +5,149 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use std::any::Any;
 use std::cell::Cell;
 use std::cell::RefCell;
 use std::cell::RefMut;
+use std::fmt::Debug;
+use std::mem;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::thread;
 use std::time::Duration;
 use std::time::SystemTime;
 
+use ButtonState;
+use CapturedTouch;
+use CursorIcon;
+use Input;
+use Key;
 use Matrix;
+use Modifiers;
+use MouseButton;
+use Navigation;
+use TouchPhase;
+use UiState;
 use WidgetId;
 
 use animations::Interpolation;
 
+/// Number of the next frame. Incremented every time `draw()` is called.
+static NEXT_FRAME: AtomicUsize = AtomicUsize::new(1);
+
 /// Start drawing your UI.
 ///
 /// This function builds a `SharedDrawContext` that can be used to build `DrawContext`s.
 pub fn draw() -> SharedDrawContext {
     SharedDrawContext {
         shared1: Arc::new(Shared1 {
+            frame: NEXT_FRAME.fetch_add(1, Ordering::Relaxed),
             next_widget_id: AtomicUsize::new(1),
             cursor_hovered_widget: AtomicBool::new(false),
-        })
+            cursor_icon: Mutex::new(None),
+        }),
+        scale_factor: 1.0,
+        safe_area_insets: [0.0; 4],
     }
 }
 
 /// A context shared between all draw contexts.
 pub struct SharedDrawContext {
     shared1: Arc<Shared1>,
+    scale_factor: f32,
+    safe_area_insets: [f32; 4],
 }
 
 impl SharedDrawContext {
+    /// Sets the number of physical pixels per logical pixel. The default value is `1.0`.
+    ///
+    /// The dimensions passed to `draw` are always in physical pixels, but all the functions that
+    /// take or return pixels (for example `DrawContext::margin_pixels`) work in logical pixels.
+    /// On a HiDPI display with a scale factor of `2.0`, a margin of 10 logical pixels covers
+    /// 20 physical pixels, so that it has the same physical size as on a regular display.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `scale_factor` is not strictly positive.
+    #[inline]
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> SharedDrawContext {
+        assert!(scale_factor > 0.0);
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Sets the insets of the viewport, in logical pixels, that may be obscured by the platform.
+    ///
+    /// For example a notch at the top of a phone screen, the overscan area of a TV, or an
+    /// on-screen keyboard at the bottom. The default value is `0.0` for each edge.
+    ///
+    /// The insets are used by `DrawContext::safe_area`.
+    #[inline]
+    pub fn with_safe_area_insets(mut self, top: f32, right: f32, bottom: f32, left: f32)
+                                 -> SharedDrawContext
+    {
+        self.safe_area_insets = [top, right, bottom, left];
+        self
+    }
+
+    /// Builds the draw context that covers the whole viewport.
+    ///
+    /// `width` and `height` are the dimensions of the viewport in physical pixels.
     ///
     /// The cursor coordinates, if any, must be in OpenGL viewport coordinates. In other words,
     /// `[-1.0, -1.0]` corresponds to the bottom-left hand corner of the screen, and `[1.0, 1.0]`
     /// to the top-right hand corner.
+    ///
+    /// This is a shortcut for `draw_with_input` when only the left button is used. Since the
+    /// button is considered down only during the frame when it's pressed, widgets that need to
+    /// know whether it's held should be drawn with `draw_with_input`.
     pub fn draw<'b, D: ?Sized + 'b>(&self, width: f32, height: f32, draw: &'b mut D,
                                     cursor: Option<[f32; 2]>, cursor_was_pressed: bool,
                                     cursor_was_released: bool) -> DrawContext<'b, D>
+    {
+        let mut input = Input::new(cursor);
+        input.left = ButtonState {
+            down: cursor_was_pressed,
+            pressed: cursor_was_pressed,
+            released: cursor_was_released,
+        };
+
+        self.draw_with_input(width, height, draw, &input)
+    }
+
+    /// Builds the draw context that covers the whole viewport, with the state of the input
+    /// devices during this frame.
+    ///
+    /// `width` and `height` are the dimensions of the viewport in physical pixels.
+    pub fn draw_with_input<'b, D: ?Sized + 'b>(&self, width: f32, height: f32, draw: &'b mut D,
+                                               input: &Input) -> DrawContext<'b, D>
     {
         DrawContext {
             matrix: Matrix::identity(),
-            width: width,
-            height: height,
+            width,
+            height,
             animation: None,
-            cursor: cursor,
-            cursor_was_pressed: cursor_was_pressed,
-            cursor_was_released: cursor_was_released,
+            cursor: input.cursor,
+            cursor_was_pressed: input.left.pressed,
+            cursor_was_released: input.left.released,
+            pixel_snapping: false,
+            layer: 0,
+            input_blocked: false,
+            disabled: false,
             shared1: self.shared1.clone(),
             shared2: Rc::new(Shared2 {
-                draw: RefCell::new(draw),
+                draw: RefCell::new(Some(draw)),
+                viewport_width: width,
+                viewport_height: height,
+                scale_factor: self.scale_factor,
+                safe_area_insets: self.safe_area_insets,
+                input: input.clone(),
                 cursor_hovered_widget: Cell::new(false),
+                deferred: RefCell::new(Vec::new()),
             }),
         }
     }
@@ -76,11 +165,30 @@ impl SharedDrawContext {
     pub fn cursor_hovered_widget(&self) -> bool {
         self.shared1.cursor_hovered_widget.load(Ordering::Relaxed)
     }
+
+    /// Returns the shape that the mouse cursor should have, according to the widgets that have
+    /// been drawn by the draw contexts.
+    ///
+    /// When you create the context, this value is initially `CursorIcon::Arrow`. Widgets can
+    /// change it with `DrawContext::request_cursor_icon`. Pass the result to the windowing system
+    /// once the UI has been drawn.
+    #[inline]
+    pub fn cursor_icon(&self) -> CursorIcon {
+        match *self.shared1.cursor_icon.lock().unwrap() {
+            Some((_, icon)) => icon,
+            None => CursorIcon::default(),
+        }
+    }
 }
 
 struct Shared1 {
+    /// Number that identifies the frame. Used by `UiState` to detect the start of a new frame.
+    frame: usize,
     next_widget_id: AtomicUsize,
     cursor_hovered_widget: AtomicBool,
+    /// Cursor icon requested with `request_cursor_icon`, and the layer of the context that
+    /// requested it.
+    cursor_icon: Mutex<Option<(u32, CursorIcon)>>,
 }
 
 /// Contains everything required to draw a widget.
@@ -105,27 +213,223 @@ pub struct DrawContext<'b, D: ?Sized + 'b> {
 
     cursor_was_pressed: bool,
     cursor_was_released: bool,
+
+    /// If true, the edges of the context are rounded to the nearest pixel of the viewport.
+    pixel_snapping: bool,
+
+    /// Layer the context belongs to. Higher layers are drawn on top of lower layers.
+    layer: u32,
+
+    /// If true, the context doesn't receive keyboard input. The cursor is blocked by setting
+    /// `cursor` to `None` and the other cursor fields to `false`.
+    input_blocked: bool,
+
+    /// If true, the widgets of the context are drawn as disabled. The input is blocked, except
+    /// that `cursor` is kept so that the context can still be hovered.
+    disabled: bool,
 }
 
 struct Shared2<'a, D: ?Sized + 'a> {
-    draw: RefCell<&'a mut D>,
+    /// The object to draw on. Always `Some`, except while the deferred draws are being flushed.
+    draw: RefCell<Option<&'a mut D>>,
+
+    /// Dimensions of the viewport in pixels.
+    viewport_width: f32,
+    viewport_height: f32,
+
+    /// Number of physical pixels per logical pixel.
+    scale_factor: f32,
+
+    /// Insets of the viewport in logical pixels, in the order top, right, bottom, left.
+    safe_area_insets: [f32; 4],
+
+    /// State of the input devices during this frame.
+    input: Input,
 
     /// True if the cursor is over an element of the UI.
     cursor_hovered_widget: Cell<bool>,
+
+    /// Draws that must happen once everything else has been drawn.
+    deferred: RefCell<Vec<Deferred<'a, D>>>,
+}
+
+type DeferredCallback<'a, D> = Box<dyn FnOnce(&DrawContext<'a, D>) + 'a>;
+
+/// A function registered with `DrawContext::draw_deferred`, and the state of the context it
+/// must be called with.
+struct Deferred<'a, D: ?Sized + 'a> {
+    callback: DeferredCallback<'a, D>,
+    shared1: Arc<Shared1>,
+    matrix: Matrix,
+    width: f32,
+    height: f32,
+    animation: Option<(Matrix, f32)>,
+    cursor: Option<[f32; 2]>,
+    cursor_was_pressed: bool,
+    cursor_was_released: bool,
+    pixel_snapping: bool,
+    layer: u32,
+    input_blocked: bool,
+    disabled: bool,
+}
+
+impl<'a, D: ?Sized + 'a> Drop for Shared2<'a, D> {
+    fn drop(&mut self) {
+        // Once the last context of the frame is destroyed, we call the deferred functions. They
+        // receive contexts that use a new `Shared2`, which is itself destroyed at the end of this
+        // function. This flushes the functions that have been deferred by the deferred functions.
+        // Nothing is drawn if the frame is being unwound, as a panic in one of the functions
+        // would then abort the process.
+        if thread::panicking() {
+            return;
+        }
+
+        let mut deferred = mem::take(self.deferred.get_mut());
+        if deferred.is_empty() {
+            return;
+        }
+
+        // The sort is stable, so functions of the same layer are called in the order in which
+        // they have been registered.
+        deferred.sort_by_key(|d| d.layer);
+
+        let draw = match self.draw.get_mut().take() {
+            Some(d) => d,
+            None => return,
+        };
+
+        let shared2 = Rc::new(Shared2 {
+            draw: RefCell::new(Some(draw)),
+            viewport_width: self.viewport_width,
+            viewport_height: self.viewport_height,
+            scale_factor: self.scale_factor,
+            safe_area_insets: self.safe_area_insets,
+            input: self.input.clone(),
+            cursor_hovered_widget: Cell::new(self.cursor_hovered_widget.get()),
+            deferred: RefCell::new(Vec::new()),
+        });
+
+        for deferred in deferred {
+            let ctxt = DrawContext {
+                matrix: deferred.matrix,
+                width: deferred.width,
+                height: deferred.height,
+                animation: deferred.animation,
+                shared1: deferred.shared1,
+                shared2: shared2.clone(),
+                cursor: deferred.cursor,
+                cursor_was_pressed: deferred.cursor_was_pressed,
+                cursor_was_released: deferred.cursor_was_released,
+                pixel_snapping: deferred.pixel_snapping,
+                layer: deferred.layer,
+                input_blocked: deferred.input_blocked,
+                disabled: deferred.disabled,
+            };
+
+            (deferred.callback)(&ctxt);
+        }
+    }
 }
 
 impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
     /// UNSTABLE. Obtains the underlying `draw` object.
     #[inline]
     #[doc(hidden)]
-    pub fn draw(&self) -> RefMut<&'b mut D> {
-        self.shared2.draw.borrow_mut()
+    pub fn draw(&self) -> RefMut<'_, &'b mut D> {
+        RefMut::map(self.shared2.draw.borrow_mut(), |d| d.as_mut().unwrap())
+    }
+
+    /// Registers a function that will be called with a copy of this context after everything
+    /// else has been drawn.
+    ///
+    /// This can be used to draw elements that must appear on top of the rest of the UI, like
+    /// popups, even though they are drawn before some other widgets. The deferred functions are
+    /// called in the order in which they have been registered, when the last `DrawContext` of the
+    /// frame is destroyed.
+    ///
+    /// Since the function is called later, it can't borrow data that doesn't live as long as the
+    /// object that is drawn on. Any data it needs must be cloned. The function must not hold a
+    /// `DrawContext` either, otherwise the frame would never end.
+    pub fn draw_deferred<F>(&self, callback: F)
+        where F: FnOnce(&DrawContext<'b, D>) + 'b
+    {
+        self.shared2.deferred.borrow_mut().push(Deferred {
+            callback: Box::new(callback),
+            shared1: self.shared1.clone(),
+            matrix: self.matrix,
+            width: self.width,
+            height: self.height,
+            animation: self.animation,
+            cursor: self.cursor,
+            cursor_was_pressed: self.cursor_was_pressed,
+            cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
+        });
+    }
+
+    /// Builds a new draw context identical to this one, but that belongs to the given input
+    /// layer.
+    ///
+    /// Widgets that use `hit_test` can't be hovered when they are covered by a widget of a higher
+    /// layer, and cursor icons requested from a higher layer win. The functions registered with
+    /// `draw_deferred` are also called in the order of their layer. All the contexts belong to
+    /// layer `0` by default.
+    ///
+    /// The layer doesn't change when the widgets are drawn. Widgets drawn directly with the new
+    /// context are drawn immediately, like with any other context. To appear above the widgets
+    /// that are drawn later, they must be drawn from a function passed to `draw_deferred`.
+    #[inline]
+    pub fn input_layer(&self, layer: u32) -> DrawContext<'b, D> {
+        DrawContext {
+            matrix: self.matrix,
+            width: self.width,
+            height: self.height,
+            animation: self.animation,
+            shared1: self.shared1.clone(),
+            shared2: self.shared2.clone(),
+            cursor: self.cursor,
+            cursor_was_pressed: self.cursor_was_pressed,
+            cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
+        }
+    }
+
+    /// Shortcut for `input_layer(current_input_layer() + 1)`. Builds a context whose widgets
+    /// cover the ones of this context.
+    #[inline]
+    pub fn input_overlay(&self) -> DrawContext<'b, D> {
+        self.input_layer(self.layer + 1)
+    }
+
+    /// Returns the input layer this context belongs to. See `input_layer`.
+    #[inline]
+    pub fn current_input_layer(&self) -> u32 {
+        self.layer
     }
 
     /// Returns a matrix that turns a fullscreen rectangle into a rectangle that covers only the
     /// context's area.
+    ///
+    /// If pixel snapping is enabled, the edges of the rectangle are rounded to whole pixels.
     #[inline]
     pub fn matrix(&self) -> Matrix {
+        let matrix = self.unsnapped_matrix();
+
+        if self.pixel_snapping {
+            snap_to_pixels(&matrix, self.shared2.viewport_width, self.shared2.viewport_height)
+        } else {
+            matrix
+        }
+    }
+
+    /// Same as `matrix()`, but without taking pixel snapping into account.
+    fn unsnapped_matrix(&self) -> Matrix {
         if let Some((matrix, percent)) = self.animation {
             // TODO: correct decomposition with https://drafts.csswg.org/css-transforms/#decomposing-a-2d-matrix
 
@@ -148,7 +452,8 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
 
     /// Returns true if the cursor went from up to down in the current frame.
     ///
-    /// This is the value that was passed when constructing the context.
+    /// This is the value that was passed when constructing the context, unless the input of the
+    /// context is blocked.
     #[inline]
     pub fn cursor_was_pressed(&self) -> bool {
         self.cursor_was_pressed
@@ -156,12 +461,111 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
 
     /// Returns true if the cursor went from down to up in the current frame.
     ///
-    /// This is the value that was passed when constructing the context.
+    /// This is the value that was passed when constructing the context, unless the input of the
+    /// context is blocked.
     #[inline]
     pub fn cursor_was_released(&self) -> bool {
         self.cursor_was_released
     }
 
+    /// Returns true if the given key has been pressed since the previous frame.
+    ///
+    /// This is the value that was passed to `SharedDrawContext::draw_with_input`, unless the
+    /// input of the context is blocked.
+    #[inline]
+    pub fn key_was_pressed(&self, key: Key) -> bool {
+        !self.input_blocked && self.shared2.input.keys.contains(&key)
+    }
+
+    /// Returns true if the given mouse button is down.
+    ///
+    /// This is the value that was passed to `SharedDrawContext::draw_with_input`, unless the
+    /// input of the context is blocked.
+    #[inline]
+    pub fn button_is_down(&self, button: MouseButton) -> bool {
+        !self.input_blocked && self.shared2.input.button(button).down
+    }
+
+    /// Returns true if the given mouse button went from up to down in the current frame.
+    ///
+    /// For the left button, this is the same as `cursor_was_pressed`. For the other buttons, this
+    /// is the value that was passed to `SharedDrawContext::draw_with_input`. In both cases,
+    /// returns false if the input of the context is blocked.
+    #[inline]
+    pub fn button_was_pressed(&self, button: MouseButton) -> bool {
+        match button {
+            MouseButton::Left => self.cursor_was_pressed,
+            _ => !self.input_blocked && self.shared2.input.button(button).pressed,
+        }
+    }
+
+    /// Returns true if the given mouse button went from down to up in the current frame.
+    ///
+    /// For the left button, this is the same as `cursor_was_released`. For the other buttons,
+    /// this is the value that was passed to `SharedDrawContext::draw_with_input`. In both cases,
+    /// returns false if the input of the context is blocked.
+    #[inline]
+    pub fn button_was_released(&self, button: MouseButton) -> bool {
+        match button {
+            MouseButton::Left => self.cursor_was_released,
+            _ => !self.input_blocked && self.shared2.input.button(button).released,
+        }
+    }
+
+    /// Returns true if the given navigation input has been pressed since the previous frame.
+    ///
+    /// This is the value that was passed to `SharedDrawContext::draw_with_input`, unless the
+    /// input of the context is blocked.
+    #[inline]
+    pub fn navigation_was_pressed(&self, navigation: Navigation) -> bool {
+        !self.input_blocked && self.shared2.input.navigation.contains(&navigation)
+    }
+
+    /// Returns the distance scrolled with the mouse wheel since the previous frame, or
+    /// `[0.0, 0.0]` if the input of the context is blocked. See `Input::wheel`.
+    #[inline]
+    pub fn wheel_delta(&self) -> [f32; 2] {
+        if self.input_blocked {
+            [0.0, 0.0]
+        } else {
+            self.shared2.input.wheel
+        }
+    }
+
+    /// Returns the modifier keys that are held during this frame.
+    #[inline]
+    pub fn modifiers(&self) -> Modifiers {
+        self.shared2.input.modifiers
+    }
+
+    /// Returns the time of the current frame, as passed to `SharedDrawContext::draw_with_input`.
+    ///
+    /// Widgets should use this instead of `SystemTime::now()`, so that all the widgets of a frame
+    /// see the same time.
+    #[inline]
+    pub fn frame_time(&self) -> SystemTime {
+        self.shared2.input.time
+    }
+
+    /// Requests the mouse cursor to have the given shape during this frame. The result can be
+    /// retreived with `SharedDrawContext::cursor_icon`.
+    ///
+    /// Widgets usually call this function while they are hovered. If several contexts request a
+    /// cursor icon, the one from the highest layer wins. Within the same layer, the last request
+    /// wins, since the widgets that are drawn last are on top of the others.
+    #[inline]
+    pub fn request_cursor_icon(&self, icon: CursorIcon) {
+        let mut current = self.shared1.cursor_icon.lock().unwrap();
+        let replace = match *current {
+            Some((layer, _)) => self.layer >= layer,
+            None => true,
+        };
+
+        if replace {
+            *current = Some((self.layer, icon));
+        }
+    }
+
     /// Returns true if one of the elements that has been drawn is under the mouse cursor.
     ///
     /// When you create the context, this value is initally false. Each widget that you draw can
@@ -189,66 +593,248 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
     /// Returns true if the cursor is currently hovering this part of the viewport.
     ///
     /// This is equivalent to `cursor_hover_coordinates().is_some()`, except more optimized.
+    ///
+    /// This function doesn't take the other widgets into account. If some other widget covers
+    /// this one, the result is still true. See `hit_test`.
     #[inline]
     pub fn is_cursor_hovering(&self) -> bool {
-        /// Calculates whether the point is in a rectangle multiplied by a matrix.
-        fn test(matrix: &Matrix, point: &[f32; 2]) -> bool {
-            // We start by calculating the positions of the four corners of the shape in viewport
-            // coordinates, so that they can be compared with the point which is already in
-            // viewport coordinates.
-
-            let top_left = *matrix * [-1.0, 1.0, 1.0];
-            let top_left = [top_left[0] / top_left[2], top_left[1] / top_left[2]];
-
-            let top_right = *matrix * [1.0, 1.0, 1.0];
-            let top_right = [top_right[0] / top_right[2], top_right[1] / top_right[2]];
-
-            let bot_left = *matrix * [-1.0, -1.0, 1.0];
-            let bot_left = [bot_left[0] / bot_left[2], bot_left[1] / bot_left[2]];
-
-            let bot_right = *matrix * [1.0, -1.0, 1.0];
-            let bot_right = [bot_right[0] / bot_right[2], bot_right[1] / bot_right[2]];
-
-            // The point is within our rectangle if and only if it is on the right side of each
-            // border of the rectangle (taken in the right order).
-            //
-            // To check this, we calculate the dot product of the vector `point - corner` with
-            // `next_corner - corner`. If the value is positive, then the angle is inferior to
-            // 90°. If the the value is negative, the angle is superior to 90° and we know that
-            // the cursor is outside of the rectangle.
-
-            if (point[0] - top_left[0]) * (top_right[0] - top_left[0]) +
-               (point[1] - top_left[1]) * (top_right[1] - top_left[1]) < 0.0
-            {
-                return false;
-            }
+        if let Some(cursor) = self.cursor {
+            is_in_rectangle(&self.matrix(), &cursor)
+        } else {
+            false
+        }
+    }
 
-            if (point[0] - top_right[0]) * (bot_right[0] - top_right[0]) +
-               (point[1] - top_right[1]) * (bot_right[1] - top_right[1]) < 0.0
-            {
-                return false;
-            }
+    /// Registers the area of this context as belonging to the given widget, then returns true if
+    /// the cursor is hovering it and it isn't covered by some other widget.
+    ///
+    /// The areas registered during the previous frame are used to determine which widget is on
+    /// top of the others. A widget is covered if the cursor is over the area of a widget that
+    /// belongs to a higher layer, or to the same layer but that was registered after it. In
+    /// other words, only the topmost widget under the cursor is considered hovered. A widget that
+    /// didn't exist during the previous frame is considered to be the topmost of its layer.
+    ///
+    /// Elements that aren't interactive, like the background of a panel, can also call this
+    /// function with a newly-reserved widget id so that they cover the widgets below them.
+    ///
+    /// If the cursor is hovering the area, this function also calls `set_cursor_hovered_widget`,
+    /// even if the widget is covered.
+    pub fn hit_test(&self, ui_state: &mut UiState, widget_id: &WidgetId) -> bool {
+        ui_state.start_frame(self.shared1.frame);
+
+        let matrix = self.matrix();
+        ui_state.next_hit_boxes.push(HitBox {
+            widget: widget_id.clone(),
+            layer: self.layer,
+            matrix,
+        });
+
+        let cursor = match self.cursor {
+            Some(c) => c,
+            None => return false,
+        };
 
-            if (point[0] - bot_right[0]) * (bot_left[0] - bot_right[0]) +
-               (point[1] - bot_right[1]) * (bot_left[1] - bot_right[1]) < 0.0
-            {
-                return false;
-            }
+        if !is_in_rectangle(&matrix, &cursor) {
+            return false;
+        }
+
+        self.set_cursor_hovered_widget();
+        !self.is_covered(ui_state, Some(widget_id), &cursor)
+    }
 
-            if (point[0] - bot_left[0]) * (top_left[0] - bot_left[0]) +
-               (point[1] - bot_left[1]) * (top_left[1] - bot_left[1]) < 0.0
-            {
-                return false;
+    /// Registers the area of this context as a widget that can receive the focus, then returns
+    /// true if the widget has the focus.
+    ///
+    /// When `Navigation::Up`, `Down`, `Left` or `Right` is pressed, the focus moves to the
+    /// nearest focusable widget in that direction, using the areas registered during the previous
+    /// frame. If no widget has the focus, it goes to the first widget that was registered.
+    /// Focused widgets usually look like they are hovered, and react to `Navigation::Accept` as
+    /// if they were clicked.
+    ///
+    /// If the input of the context is blocked, the widget isn't registered and can't have the
+    /// focus.
+    pub fn focusable(&self, ui_state: &mut UiState, widget_id: &WidgetId) -> bool {
+        if self.input_blocked {
+            return false;
+        }
+
+        ui_state.start_frame(self.shared1.frame);
+
+        if ui_state.navigation_frame != self.shared1.frame {
+            ui_state.navigation_frame = self.shared1.frame;
+            let directions = [Navigation::Up, Navigation::Down, Navigation::Left,
+                              Navigation::Right];
+            if let Some(&direction) = directions.iter().find(|&&d| self.navigation_was_pressed(d)) {
+                if let Some(widget) = navigate(ui_state, direction) {
+                    ui_state.focused_widget = Some(widget);
+                }
             }
+        }
+
+        ui_state.next_focus_boxes.push(FocusBox {
+            widget: widget_id.clone(),
+            rect: self.rect_pixels(),
+        });
+
+        ui_state.focused_widget.as_ref() == Some(widget_id)
+    }
+
+    /// Returns the value of type `T` stored in the `UiState` for the given widget, inserting the
+    /// result of `default` if there is none.
+    ///
+    /// This lets widgets remember things from one frame to the next, like a scroll offset or
+    /// the moment when an animation started, without the caller having to store them. Each
+    /// widget can store one value of each type.
+    ///
+    /// The values are dropped if this function isn't called for them during a frame. In other
+    /// words, the values of the widgets that aren't drawn anymore are garbage-collected.
+    ///
+    /// The values must be `Clone`, `Debug` and `PartialEq` so that the `UiState` keeps
+    /// implementing these traits, and `Send + Sync` so that it can still be sent to and shared
+    /// with other threads.
+    pub fn widget_data<'s, T, F>(&self, ui_state: &'s mut UiState, widget_id: &WidgetId,
+                                 default: F) -> &'s mut T
+        where T: Any + Clone + Debug + PartialEq + Send + Sync, F: FnOnce() -> T
+    {
+        ui_state.start_frame(self.shared1.frame);
+        ui_state.storage.get_or_insert_with(widget_id, self.shared1.frame, default)
+    }
 
-            true
+    /// Returns true if the cursor is hovering this context and if it isn't covered by a widget of
+    /// a higher layer. Contrary to `hit_test`, the area of the context isn't registered.
+    ///
+    /// This can be used to react to the cursor without preventing the widgets of the same layer
+    /// from being hovered.
+    pub fn is_cursor_hovering_uncovered(&self, ui_state: &mut UiState) -> bool {
+        ui_state.start_frame(self.shared1.frame);
+
+        match self.cursor {
+            Some(cursor) => {
+                is_in_rectangle(&self.matrix(), &cursor) &&
+                !self.is_covered(ui_state, None, &cursor)
+            },
+            None => false,
         }
+    }
 
-        if let Some(cursor) = self.cursor {
-            test(&self.matrix(), &cursor)
-        } else {
-            false
+    /// Returns the touches that are captured by the widget, and captures the touches that start
+    /// over this context.
+    ///
+    /// A touch is captured by the widget if it starts over the context and if no other widget
+    /// covers the context at this position, like with `hit_test`. The area of the widget must have
+    /// been registered with `hit_test`, otherwise it is considered to be above the other widgets
+    /// of its layer. The touch then stays captured until it ends, even if it moves outside of the
+    /// context. This makes it possible for several widgets to be manipulated at the same time
+    /// with different fingers.
+    ///
+    /// The touches that end during this frame are returned one last time with their final
+    /// position. If the input of the context is blocked, no touch is captured.
+    pub fn captured_touches(&self, ui_state: &mut UiState, widget_id: &WidgetId)
+                            -> Vec<CapturedTouch>
+    {
+        ui_state.start_frame(self.shared1.frame);
+
+        // Forgetting about the touches that the platform no longer reports.
+        let touches = &self.shared2.input.touches;
+        ui_state.touch_captures.retain(|c| touches.iter().any(|t| t.id == c.touch));
+
+        if self.input_blocked {
+            return Vec::new();
+        }
+
+        let matrix = self.matrix();
+        let mut result = Vec::new();
+
+        for touch in touches {
+            let position = self.to_pixels(&touch.position);
+            let ended = touch.phase == TouchPhase::Ended || touch.phase == TouchPhase::Cancelled;
+
+            match ui_state.touch_captures.iter().position(|c| c.touch == touch.id) {
+                Some(index) => {
+                    if ui_state.touch_captures[index].widget != *widget_id {
+                        continue;
+                    }
+
+                    let previous_position = ui_state.touch_captures[index].position;
+                    if ended {
+                        ui_state.touch_captures.remove(index);
+                    } else {
+                        ui_state.touch_captures[index].position = position;
+                    }
+
+                    result.push(CapturedTouch {
+                        id: touch.id,
+                        position,
+                        previous_position,
+                        phase: touch.phase,
+                    });
+                },
+
+                None => {
+                    if touch.phase != TouchPhase::Started ||
+                       !is_in_rectangle(&matrix, &touch.position) ||
+                       self.is_covered(ui_state, Some(widget_id), &touch.position)
+                    {
+                        continue;
+                    }
+
+                    ui_state.touch_captures.push(TouchCapture {
+                        touch: touch.id,
+                        widget: widget_id.clone(),
+                        position,
+                    });
+
+                    result.push(CapturedTouch {
+                        id: touch.id,
+                        position,
+                        previous_position: position,
+                        phase: touch.phase,
+                    });
+                },
+            }
         }
+
+        result
+    }
+
+    /// Returns true if one of the areas registered during the previous frame covers the given
+    /// widget at the position of the cursor.
+    fn is_covered(&self, ui_state: &UiState, widget_id: Option<&WidgetId>, cursor: &[f32; 2])
+                  -> bool
+    {
+        // Position of the widget in the previous frame, or `None` if it wasn't registered.
+        let position = match widget_id {
+            Some(id) => ui_state.hit_boxes.iter().rposition(|b| b.widget == *id),
+            None => None,
+        };
+
+        ui_state.hit_boxes.iter().enumerate().any(|(num, b)| {
+            let above = b.layer > self.layer || (b.layer == self.layer && match position {
+                Some(p) => num > p,
+                None => false,
+            });
+
+            above && Some(&b.widget) != widget_id && is_in_rectangle(&b.matrix, cursor)
+        })
+    }
+
+    /// Returns the position of the cursor in the viewport, in logical pixels, or `None` if there
+    /// is no cursor.
+    ///
+    /// Uses the same coordinate system as `corners_pixels`.
+    #[inline]
+    pub fn cursor_position_pixels(&self) -> Option<[f32; 2]> {
+        self.cursor.map(|c| self.to_pixels(&c))
+    }
+
+    /// Turns a position in OpenGL viewport coordinates into a position in logical pixels, in the
+    /// coordinate system of `corners_pixels`.
+    #[inline]
+    fn to_pixels(&self, point: &[f32; 2]) -> [f32; 2] {
+        [
+            (point[0] + 1.0) * 0.5 * self.shared2.viewport_width / self.shared2.scale_factor,
+            (1.0 - point[1]) * 0.5 * self.shared2.viewport_height / self.shared2.scale_factor,
+        ]
     }
 
     /// If the cursor is hovering the context, returns the coordinates of the cursor within the
@@ -256,6 +842,7 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
     ///
     /// The result is in OpenGL-like coordinates. In other words, (-1,-1) is the bottom-left hand
     /// corner and (1,1) is the top-right hand corner.
+    #[allow(clippy::question_mark, clippy::eq_op)]
     pub fn cursor_hover_coordinates(&self) -> Option<[f32; 2]> {
         // we compute the inverse of the matrix
         let m = match self.matrix().invert() {
@@ -292,6 +879,183 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
         self.width / self.height
     }
 
+    /// Returns the number of physical pixels per logical pixel, as passed to
+    /// `SharedDrawContext::with_scale_factor`.
+    #[inline]
+    pub fn scale_factor(&self) -> f32 {
+        self.shared2.scale_factor
+    }
+
+    /// Returns the width of the context in logical pixels.
+    #[inline]
+    pub fn width_pixels(&self) -> f32 {
+        self.width / self.shared2.scale_factor
+    }
+
+    /// Returns the height of the context in logical pixels.
+    #[inline]
+    pub fn height_pixels(&self) -> f32 {
+        self.height / self.shared2.scale_factor
+    }
+
+    /// Returns the positions of the four corners of the context in the viewport, in logical
+    /// pixels, in the order top-left, top-right, bottom-right, bottom-left.
+    ///
+    /// `[0.0, 0.0]` is the top-left hand corner of the viewport, and the Y coordinate goes
+    /// towards the bottom. This is the coordinate system that windowing systems usually use,
+    /// which makes it possible to position native elements over a part of the UI.
+    ///
+    /// Contrary to `width_pixels` and `height_pixels`, the corners take the current animation
+    /// and the pixel snapping into account.
+    pub fn corners_pixels(&self) -> [[f32; 2]; 4] {
+        let matrix = self.matrix();
+        let width = self.shared2.viewport_width / self.shared2.scale_factor;
+        let height = self.shared2.viewport_height / self.shared2.scale_factor;
+        let to_pixels = |x: f32, y: f32| {
+            let p = matrix * [x, y, 1.0];
+            [(p[0] / p[2] + 1.0) * 0.5 * width, (1.0 - p[1] / p[2]) * 0.5 * height]
+        };
+
+        [to_pixels(-1.0, 1.0), to_pixels(1.0, 1.0), to_pixels(1.0, -1.0), to_pixels(-1.0, -1.0)]
+    }
+
+    /// Returns the rectangle covered by the context in the viewport, in logical pixels.
+    ///
+    /// Uses the same coordinate system as `corners_pixels`. If the context is rotated, the
+    /// rectangle is the smallest one that contains all four corners.
+    pub fn rect_pixels(&self) -> Rect {
+        let corners = self.corners_pixels();
+
+        let mut min = corners[0];
+        let mut max = corners[0];
+        for corner in &corners[1..] {
+            min = [min[0].min(corner[0]), min[1].min(corner[1])];
+            max = [max[0].max(corner[0]), max[1].max(corner[1])];
+        }
+
+        Rect {
+            x: min[0],
+            y: min[1],
+            width: max[0] - min[0],
+            height: max[1] - min[1],
+        }
+    }
+
+    /// Builds a new draw context that covers the whole viewport, whatever the area of the
+    /// current context is.
+    ///
+    /// The layer and the pixel snapping of the current context are kept. This can be used for
+    /// example to draw elements that follow the cursor.
+    #[inline]
+    pub fn viewport(&self) -> DrawContext<'b, D> {
+        DrawContext {
+            matrix: Matrix::identity(),
+            width: self.shared2.viewport_width,
+            height: self.shared2.viewport_height,
+            animation: None,
+            shared1: self.shared1.clone(),
+            shared2: self.shared2.clone(),
+            cursor: self.cursor,
+            cursor_was_pressed: self.cursor_was_pressed,
+            cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
+        }
+    }
+
+    /// Builds a new draw context identical to this one, but that doesn't receive any input.
+    ///
+    /// The cursor is considered absent, and `cursor_was_pressed`, `cursor_was_released` and
+    /// `key_was_pressed` always return false. This also applies to all the contexts derived from
+    /// the new one. This can be used to prevent the user from interacting with a part of the UI,
+    /// for example while a dialog is open.
+    #[inline]
+    pub fn block_input(&self) -> DrawContext<'b, D> {
+        DrawContext {
+            matrix: self.matrix,
+            width: self.width,
+            height: self.height,
+            animation: self.animation,
+            shared1: self.shared1.clone(),
+            shared2: self.shared2.clone(),
+            cursor: None,
+            cursor_was_pressed: false,
+            cursor_was_released: false,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: true,
+            disabled: self.disabled,
+        }
+    }
+
+    /// Builds a new draw context identical to this one, but where the widgets are disabled if
+    /// `disabled` is true.
+    ///
+    /// Disabled widgets are drawn differently and don't react to the input: `cursor_was_pressed`,
+    /// `cursor_was_released`, `key_was_pressed` and the other input functions always return
+    /// false, so that they never become the active widget nor receive the focus. Contrary to
+    /// `block_input`, the cursor is still there, so that the context can still be hovered, for
+    /// example to show a tooltip explaining why a button is disabled.
+    ///
+    /// This applies to all the contexts derived from the new one. Passing false has no effect if
+    /// the context is already disabled.
+    #[inline]
+    pub fn disabled(&self, disabled: bool) -> DrawContext<'b, D> {
+        let disabled = self.disabled || disabled;
+
+        DrawContext {
+            matrix: self.matrix,
+            width: self.width,
+            height: self.height,
+            animation: self.animation,
+            shared1: self.shared1.clone(),
+            shared2: self.shared2.clone(),
+            cursor: self.cursor,
+            cursor_was_pressed: self.cursor_was_pressed && !disabled,
+            cursor_was_released: self.cursor_was_released && !disabled,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked || disabled,
+            disabled,
+        }
+    }
+
+    /// Returns true if the widgets of this context must be drawn as disabled. See `disabled`.
+    #[inline]
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Builds a new draw context identical to this one, but with pixel snapping enabled or
+    /// disabled.
+    ///
+    /// When pixel snapping is enabled, the edges of the context and of all the contexts derived
+    /// from it are rounded to the nearest pixel of the viewport. This avoids blurry borders when
+    /// drawing thin elements, at the cost of small inaccuracies in the layout. Enable it on the
+    /// context returned by `SharedDrawContext::draw` to apply it to the whole UI.
+    ///
+    /// Pixel snapping has no effect on contexts that are rotated or skewed.
+    #[inline]
+    pub fn pixel_snapping(&self, enabled: bool) -> DrawContext<'b, D> {
+        DrawContext {
+            matrix: self.matrix,
+            width: self.width,
+            height: self.height,
+            animation: self.animation,
+            shared1: self.shared1.clone(),
+            shared2: self.shared2.clone(),
+            cursor: self.cursor,
+            cursor_was_pressed: self.cursor_was_pressed,
+            cursor_was_released: self.cursor_was_released,
+            pixel_snapping: enabled,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
+        }
+    }
+
     /// Builds a new draw context containing a subarea of the current context, but with a margin.
     ///
     /// The margin is expressed in percentage of the surface (between 0.0 and 1.0).
@@ -308,6 +1072,10 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
             cursor: self.cursor,
             cursor_was_pressed: self.cursor_was_pressed,
             cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
         }
     }
 
@@ -331,6 +1099,51 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
         self.margin(top / hpw, right / wph, bottom / hpw, left / wph)
     }
 
+    /// Builds a new draw context containing a subarea of the current context, but with a margin.
+    ///
+    /// The margin is expressed in logical pixels.
+    #[inline]
+    pub fn margin_pixels(&self, top: f32, right: f32, bottom: f32, left: f32)
+                         -> DrawContext<'b, D>
+    {
+        let scale = self.shared2.scale_factor;
+        self.margin(top * scale / self.height, right * scale / self.width,
+                    bottom * scale / self.height, left * scale / self.width)
+    }
+
+    /// Builds a new draw context containing the part of the current context that isn't covered
+    /// by the insets passed to `SharedDrawContext::with_safe_area_insets`.
+    ///
+    /// If the current context doesn't overlap any of the insets, the new context covers the same
+    /// area as the current one.
+    pub fn safe_area(&self) -> DrawContext<'b, D> {
+        let insets = self.shared2.safe_area_insets;
+        let scale = self.shared2.scale_factor;
+
+        // Boundaries of the safe area in viewport coordinates.
+        let safe_top = 1.0 - 2.0 * insets[0] * scale / self.shared2.viewport_height;
+        let safe_right = 1.0 - 2.0 * insets[1] * scale / self.shared2.viewport_width;
+        let safe_bottom = -1.0 + 2.0 * insets[2] * scale / self.shared2.viewport_height;
+        let safe_left = -1.0 + 2.0 * insets[3] * scale / self.shared2.viewport_width;
+
+        // Boundaries of the current context in viewport coordinates.
+        let m = self.matrix.0;
+        let half_width = m[0][0].abs();
+        let half_height = m[1][1].abs();
+        let top = m[2][1] + half_height;
+        let right = m[2][0] + half_width;
+        let bottom = m[2][1] - half_height;
+        let left = m[2][0] - half_width;
+
+        #[inline]
+        fn clamp(v: f32) -> f32 { v.clamp(0.0, 1.0) }
+
+        self.margin(clamp((top - safe_top) / (2.0 * half_height)),
+                    clamp((right - safe_right) / (2.0 * half_width)),
+                    clamp((safe_bottom - bottom) / (2.0 * half_height)),
+                    clamp((safe_left - left) / (2.0 * half_width)))
+    }
+
     /// Modifies the layout so that the given width per height ratio is respected. The size of the
     /// new viewport will always been equal or small to the existing viewport.
     ///
@@ -377,6 +1190,7 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
     ///
     /// The alignment is used to determine the position of the new viewport inside the old one.
     #[inline]
+    #[allow(clippy::match_ref_pats)]
     pub fn vertical_rescale(&self, scale: f32, alignment: &VerticalAlignment)
                             -> DrawContext<'b, D>
     {
@@ -396,6 +1210,10 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
             cursor: self.cursor,
             cursor_was_pressed: self.cursor_was_pressed,
             cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
         }
     }
 
@@ -405,6 +1223,7 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
     ///
     /// The alignment is used to determine the position of the new viewport inside the old one.
     #[inline]
+    #[allow(clippy::match_ref_pats)]
     pub fn horizontal_rescale(&self, scale: f32, alignment: &HorizontalAlignment)
                               -> DrawContext<'b, D>
     {
@@ -424,9 +1243,31 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
             cursor: self.cursor,
             cursor_was_pressed: self.cursor_was_pressed,
             cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
         }
     }
 
+    /// Same as `vertical_rescale`, except that the new height is expressed in logical pixels.
+    ///
+    /// This can be used for example to draw text with a fixed size.
+    #[inline]
+    pub fn vertical_rescale_pixels(&self, height: f32, alignment: &VerticalAlignment)
+                                   -> DrawContext<'b, D>
+    {
+        self.vertical_rescale(height * self.shared2.scale_factor / self.height, alignment)
+    }
+
+    /// Same as `horizontal_rescale`, except that the new width is expressed in logical pixels.
+    #[inline]
+    pub fn horizontal_rescale_pixels(&self, width: f32, alignment: &HorizontalAlignment)
+                                     -> DrawContext<'b, D>
+    {
+        self.horizontal_rescale(width * self.shared2.scale_factor / self.width, alignment)
+    }
+
     /// Splits the viewport in `splits` vertical chunks of equal size.
     #[inline]
     pub fn vertical_split<'a>(&'a self, splits: usize) -> SplitsIter<'a, 'b, OneGen, D> {
@@ -471,10 +1312,10 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
 
         SplitsIter {
             parent: self,
-            weights: weights,
-            total_weight_inverse: total_weight_inverse,
+            weights,
+            total_weight_inverse,
             current_offset: 0.0,
-            vertical: vertical,
+            vertical,
         }
     }
 
@@ -511,9 +1352,113 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
             cursor: self.cursor,
             cursor_was_pressed: self.cursor_was_pressed,
             cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
         }
     }
 
+    /// Builds a new draw context at an explicit position inside the current context.
+    ///
+    /// `x` and `y` are the coordinates of a point within the current context, where `[0.0, 0.0]`
+    /// is the top-left hand corner and `[1.0, 1.0]` is the bottom-right hand corner. The new
+    /// context is positioned so that its point designated by `anchor` is at these coordinates.
+    /// For example with `Alignment::top_right()` the top-right hand corner of the new context
+    /// will be at `[x, y]`.
+    ///
+    /// The dimensions are a percentage of the current dimensions, like for `rescale`.
+    #[inline]
+    pub fn absolute(&self, x: f32, y: f32, width_percent: f32, height_percent: f32,
+                    anchor: &Alignment) -> DrawContext<'b, D>
+    {
+        let left = match anchor.horizontal {
+            HorizontalAlignment::Left => x,
+            HorizontalAlignment::Center => x - width_percent * 0.5,
+            HorizontalAlignment::Right => x - width_percent,
+        };
+
+        let top = match anchor.vertical {
+            VerticalAlignment::Top => y,
+            VerticalAlignment::Center => y - height_percent * 0.5,
+            VerticalAlignment::Bottom => y - height_percent,
+        };
+
+        let center_x = (left + width_percent * 0.5) * 2.0 - 1.0;
+        let center_y = 1.0 - (top + height_percent * 0.5) * 2.0;
+
+        DrawContext {
+            matrix: self.matrix * Matrix::translate(center_x, center_y)
+                                * Matrix::scale_wh(width_percent, height_percent),
+            width: self.width * width_percent,
+            height: self.height * height_percent,
+            animation: self.animation,
+            shared1: self.shared1.clone(),
+            shared2: self.shared2.clone(),
+            cursor: self.cursor,
+            cursor_was_pressed: self.cursor_was_pressed,
+            cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
+        }
+    }
+
+    /// Same as `absolute`, except that the position and the dimensions are expressed in logical
+    /// pixels.
+    ///
+    /// `[0.0, 0.0]` is the top-left hand corner of the current context.
+    #[inline]
+    pub fn absolute_pixels(&self, x: f32, y: f32, width: f32, height: f32, anchor: &Alignment)
+                           -> DrawContext<'b, D>
+    {
+        let scale = self.shared2.scale_factor;
+        self.absolute(x * scale / self.width, y * scale / self.height, width * scale / self.width,
+                      height * scale / self.height, anchor)
+    }
+
+    /// Builds a new draw context whose corner or border designated by `anchor` is attached to
+    /// the same corner or border of the current context.
+    ///
+    /// The offsets move the new context away from the attached corner or border, towards the
+    /// inside of the current context. If the alignment is centered, a positive offset moves the
+    /// new context towards the right or towards the bottom.
+    ///
+    /// The offsets and the dimensions are a percentage of the current dimensions.
+    pub fn anchor(&self, anchor: &Alignment, x_offset: f32, y_offset: f32, width_percent: f32,
+                  height_percent: f32) -> DrawContext<'b, D>
+    {
+        let x = match anchor.horizontal {
+            HorizontalAlignment::Left => x_offset,
+            HorizontalAlignment::Center => 0.5 + x_offset,
+            HorizontalAlignment::Right => 1.0 - x_offset,
+        };
+
+        let y = match anchor.vertical {
+            VerticalAlignment::Top => y_offset,
+            VerticalAlignment::Center => 0.5 + y_offset,
+            VerticalAlignment::Bottom => 1.0 - y_offset,
+        };
+
+        self.absolute(x, y, width_percent, height_percent, anchor)
+    }
+
+    /// Same as `anchor`, except that the offsets and the dimensions are expressed in logical
+    /// pixels.
+    ///
+    /// For example `anchor_pixels(&Alignment::top_right(), 10.0, 10.0, 200.0, 50.0)` builds a
+    /// context of 200x50 pixels whose top-right hand corner is 10 pixels away from the top and
+    /// the right of the current context.
+    #[inline]
+    pub fn anchor_pixels(&self, anchor: &Alignment, x_offset: f32, y_offset: f32, width: f32,
+                         height: f32) -> DrawContext<'b, D>
+    {
+        let scale = self.shared2.scale_factor;
+        self.anchor(anchor, x_offset * scale / self.width, y_offset * scale / self.height,
+                    width * scale / self.width, height * scale / self.height)
+    }
+
     /// Starts an animation. The interpolation, start time and duration are used to calculate
     /// at which point of the animation we are.
     ///
@@ -529,10 +1474,8 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
                               -> DrawContext<'b, D>
         where I: Interpolation
     {
-        let now = SystemTime::now();
-
-        let interpolation = interpolation.calculate(now, start_time, duration) as f32;
-        let current_matrix = self.matrix();
+        let interpolation = interpolation.calculate(self.frame_time(), start_time, duration) as f32;
+        let current_matrix = self.unsnapped_matrix();
 
         DrawContext {
             matrix: self.matrix,
@@ -544,6 +1487,10 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
             cursor: self.cursor,
             cursor_was_pressed: self.cursor_was_pressed,
             cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
         }
     }
 
@@ -551,7 +1498,7 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
     #[inline]
     pub fn animation_stop(&self) -> DrawContext<'b, D> {
         DrawContext {
-            matrix: self.matrix(),
+            matrix: self.unsnapped_matrix(),
             width: self.width,
             height: self.height,
             animation: None,
@@ -560,10 +1507,15 @@ impl<'b, D: ?Sized + 'b> DrawContext<'b, D> {
             cursor: self.cursor,
             cursor_was_pressed: self.cursor_was_pressed,
             cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
         }
     }
 }
 
+#[allow(clippy::extra_unused_lifetimes, clippy::clone_on_copy)]
 impl<'a, 'b, D: ?Sized + 'b> Clone for DrawContext<'b, D> {
     fn clone(&self) -> DrawContext<'b, D> {
         DrawContext {
@@ -576,12 +1528,246 @@ impl<'a, 'b, D: ?Sized + 'b> Clone for DrawContext<'b, D> {
             cursor: self.cursor.clone(),
             cursor_was_pressed: self.cursor_was_pressed,
             cursor_was_released: self.cursor_was_released,
+            pixel_snapping: self.pixel_snapping,
+            layer: self.layer,
+            input_blocked: self.input_blocked,
+            disabled: self.disabled,
         }
     }
 }
 
+/// Calculates whether the point is in a rectangle multiplied by a matrix.
+fn is_in_rectangle(matrix: &Matrix, point: &[f32; 2]) -> bool {
+    // We start by calculating the positions of the four corners of the shape in viewport
+    // coordinates, so that they can be compared with the point which is already in
+    // viewport coordinates.
+
+    let top_left = *matrix * [-1.0, 1.0, 1.0];
+    let top_left = [top_left[0] / top_left[2], top_left[1] / top_left[2]];
+
+    let top_right = *matrix * [1.0, 1.0, 1.0];
+    let top_right = [top_right[0] / top_right[2], top_right[1] / top_right[2]];
+
+    let bot_left = *matrix * [-1.0, -1.0, 1.0];
+    let bot_left = [bot_left[0] / bot_left[2], bot_left[1] / bot_left[2]];
+
+    let bot_right = *matrix * [1.0, -1.0, 1.0];
+    let bot_right = [bot_right[0] / bot_right[2], bot_right[1] / bot_right[2]];
+
+    // The point is within our rectangle if and only if it is on the right side of each
+    // border of the rectangle (taken in the right order).
+    //
+    // To check this, we calculate the dot product of the vector `point - corner` with
+    // `next_corner - corner`. If the value is positive, then the angle is inferior to
+    // 90°. If the the value is negative, the angle is superior to 90° and we know that
+    // the cursor is outside of the rectangle.
+
+    if (point[0] - top_left[0]) * (top_right[0] - top_left[0]) +
+       (point[1] - top_left[1]) * (top_right[1] - top_left[1]) < 0.0
+    {
+        return false;
+    }
+
+    if (point[0] - top_right[0]) * (bot_right[0] - top_right[0]) +
+       (point[1] - top_right[1]) * (bot_right[1] - top_right[1]) < 0.0
+    {
+        return false;
+    }
+
+    if (point[0] - bot_right[0]) * (bot_left[0] - bot_right[0]) +
+       (point[1] - bot_right[1]) * (bot_left[1] - bot_right[1]) < 0.0
+    {
+        return false;
+    }
+
+    if (point[0] - bot_left[0]) * (top_left[0] - bot_left[0]) +
+       (point[1] - bot_left[1]) * (top_left[1] - bot_left[1]) < 0.0
+    {
+        return false;
+    }
+
+    true
+}
+
+/// An area registered with `DrawContext::hit_test`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HitBox {
+    widget: WidgetId,
+    layer: u32,
+    matrix: Matrix,
+}
+
+/// An area registered with `DrawContext::focusable`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FocusBox {
+    widget: WidgetId,
+    rect: Rect,
+}
+
+/// A mouse button that has been pressed over a widget and not released yet. See
+/// `widgets::interact`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Press {
+    /// Widget the button has been pressed over.
+    pub widget: WidgetId,
+    /// Button that has been pressed.
+    pub button: MouseButton,
+    /// Moment when the button has been pressed.
+    pub start: SystemTime,
+    /// Position of the cursor during the previous frame, in logical pixels.
+    pub last_position: Option<[f32; 2]>,
+    /// True if the press has already been reported as a long press.
+    pub long_press_reported: bool,
+}
+
+/// A touch captured by a widget. See `DrawContext::captured_touches`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TouchCapture {
+    /// Identifier of the touch.
+    pub touch: u64,
+    /// Widget that has captured the touch.
+    pub widget: WidgetId,
+    /// Position of the touch during the previous frame, in logical pixels.
+    pub position: [f32; 2],
+}
+
+/// A drag started with `widgets::drag_drop::source`.
+#[derive(Clone, Debug)]
+pub struct Drag {
+    /// Widget the drag started from.
+    pub source: WidgetId,
+    /// Data that is being dragged.
+    pub payload: Arc<dyn Any + Send + Sync>,
+    /// Position of the cursor when the button was pressed, in logical pixels.
+    pub origin: [f32; 2],
+    /// Position of the cursor relative to the top-left hand corner of the source, in logical
+    /// pixels.
+    pub grab_offset: [f32; 2],
+    /// Dimensions of the source, in logical pixels.
+    pub size: [f32; 2],
+    /// False until the cursor has moved far enough from `origin`.
+    pub moving: bool,
+    /// True if the button has been released during this frame. The drag is then removed at the
+    /// start of the next frame.
+    pub released: bool,
+}
+
+impl PartialEq for Drag {
+    fn eq(&self, other: &Drag) -> bool {
+        self.source == other.source && Arc::ptr_eq(&self.payload, &other.payload) &&
+        self.origin == other.origin && self.grab_offset == other.grab_offset &&
+        self.size == other.size && self.moving == other.moving &&
+        self.released == other.released
+    }
+}
+
+impl UiState {
+    /// Called at the start of each function that uses the state from one frame to the next. If
+    /// a new frame has started, the data gathered during the previous frame becomes the
+    /// reference.
+    fn start_frame(&mut self, frame: usize) {
+        if self.frame == frame {
+            return;
+        }
+
+        // The values of the widgets that haven't been drawn during the previous frame are
+        // dropped.
+        self.storage.collect(self.frame);
+
+        self.frame = frame;
+        self.hit_boxes = mem::take(&mut self.next_hit_boxes);
+        self.focus_boxes = mem::take(&mut self.next_focus_boxes);
+
+        if let Some(true) = self.drag.as_ref().map(|d| d.released) {
+            self.drag = None;
+        }
+    }
+}
+
+/// Returns the widget that must receive the focus when moving in the given direction from the
+/// widget that has the focus, according to the areas registered during the previous frame.
+fn navigate(ui_state: &UiState, direction: Navigation) -> Option<WidgetId> {
+    let center = |r: &Rect| [r.x + r.width * 0.5, r.y + r.height * 0.5];
+
+    let current = match ui_state.focused_widget {
+        Some(ref id) => ui_state.focus_boxes.iter().find(|b| b.widget == *id),
+        None => None,
+    };
+
+    let current = match current {
+        Some(c) => center(&c.rect),
+        None => return ui_state.focus_boxes.first().map(|b| b.widget.clone()),
+    };
+
+    // Among the widgets in the given direction, we pick the one with the lowest distance along
+    // the direction plus twice the distance across it, so that aligned widgets are preferred.
+    ui_state.focus_boxes.iter().filter_map(|b| {
+        let c = center(&b.rect);
+        let (along, across) = match direction {
+            Navigation::Up => (current[1] - c[1], c[0] - current[0]),
+            Navigation::Down => (c[1] - current[1], c[0] - current[0]),
+            Navigation::Left => (current[0] - c[0], c[1] - current[1]),
+            Navigation::Right => (c[0] - current[0], c[1] - current[1]),
+            _ => return None,
+        };
+
+        if along <= 0.0 {
+            return None;
+        }
+
+        Some((along + 2.0 * across.abs(), &b.widget))
+    }).fold(None, |best: Option<(f32, &WidgetId)>, candidate| {
+        match best {
+            Some(b) if b.0 <= candidate.0 => Some(b),
+            _ => Some(candidate),
+        }
+    }).map(|(_, widget)| widget.clone())
+}
+
+/// Rounds the edges of the rectangle described by `matrix` to the nearest pixel of a viewport
+/// of the given dimensions.
+fn snap_to_pixels(matrix: &Matrix, viewport_width: f32, viewport_height: f32) -> Matrix {
+    let m = matrix.0;
+
+    // Only rectangles that are aligned with the viewport can be snapped.
+    if m[0][1] != 0.0 || m[1][0] != 0.0 {
+        return *matrix;
+    }
+
+    #[inline]
+    fn snap(center: f32, half_size: f32, viewport: f32) -> (f32, f32) {
+        let round = |v: f32| ((v + 1.0) * 0.5 * viewport).round() / viewport * 2.0 - 1.0;
+        let min = round(center - half_size);
+        let max = round(center + half_size);
+        ((min + max) * 0.5, (max - min) * 0.5)
+    }
+
+    let (x, width) = snap(m[2][0], m[0][0], viewport_width);
+    let (y, height) = snap(m[2][1], m[1][1], viewport_height);
+
+    Matrix([
+        [width, 0.0],
+        [0.0, height],
+        [x, y],
+    ])
+}
+
+/// A rectangle in the viewport. See `DrawContext::rect_pixels`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rect {
+    /// Distance between the left border of the viewport and the left border of the rectangle.
+    pub x: f32,
+    /// Distance between the top border of the viewport and the top border of the rectangle.
+    pub y: f32,
+    /// Width of the rectangle.
+    pub width: f32,
+    /// Height of the rectangle.
+    pub height: f32,
+}
+
 /// Represents the alignment of a viewport.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Alignment {
     /// The horizontal alignment.
     pub horizontal: HorizontalAlignment,
@@ -674,6 +1860,7 @@ impl Alignment {
 
 /// Describes a horizontal alignment.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum HorizontalAlignment {
     /// Align in the middle.
     Center,
@@ -685,6 +1872,7 @@ pub enum HorizontalAlignment {
 
 /// Describes a vertical alignment.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum VerticalAlignment {
     /// Align in the middle.
     Center,
@@ -708,6 +1896,7 @@ impl<'a, 'b: 'a, I, D: ?Sized + 'b> Iterator for SplitsIter<'a, 'b, I, D>
 {
     type Item = DrawContext<'b, D>;
 
+    #[allow(clippy::question_mark)]
     fn next(&mut self) -> Option<Self::Item> {
         let weight = match self.weights.next() {
             Some(w) => w,
@@ -745,6 +1934,10 @@ impl<'a, 'b: 'a, I, D: ?Sized + 'b> Iterator for SplitsIter<'a, 'b, I, D>
             cursor: self.parent.cursor,
             cursor_was_pressed: self.parent.cursor_was_pressed,
             cursor_was_released: self.parent.cursor_was_released,
+            pixel_snapping: self.parent.pixel_snapping,
+            layer: self.parent.layer,
+            input_blocked: self.parent.input_blocked,
+            disabled: self.parent.disabled,
         })
     }
 
@@ -785,3 +1978,324 @@ impl Iterator for OneGen {
 
 impl ExactSizeIterator for OneGen {
 }
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use Alignment;
+    use CursorIcon;
+    use DrawImage;
+    use Gesture;
+    use HorizontalAlignment;
+    use Input;
+    use Matrix;
+    use Navigation;
+    use Touch;
+    use TouchPhase;
+    use UiState;
+    use VerticalAlignment;
+
+    /// Drawing object that records the names of the images that are drawn.
+    struct Recorder(Vec<String>);
+
+    impl DrawImage<str> for Recorder {
+        fn draw_triangle(&mut self, _: &str, _: &Matrix, _: [[f32; 2]; 3]) {}
+
+        fn draw_image(&mut self, name: &str, _: &Matrix) {
+            self.0.push(name.to_owned());
+        }
+
+        fn get_image_width_per_height(&mut self, _: &str) -> f32 { 1.0 }
+    }
+
+    #[test]
+    fn anchor_pixels() {
+        let mut drawer = ();
+        let ctxt = ::draw().draw(1000.0, 500.0, &mut drawer, None, false, false);
+        let ctxt = ctxt.anchor_pixels(&Alignment::top_right(), 10.0, 10.0, 200.0, 50.0);
+
+        let top_right = ctxt.matrix() * [1.0, 1.0, 1.0];
+        assert!((top_right[0] - 0.98).abs() < 0.0001);
+        assert!((top_right[1] - 0.96).abs() < 0.0001);
+
+        let bottom_left = ctxt.matrix() * [-1.0, -1.0, 1.0];
+        assert!((bottom_left[0] - 0.58).abs() < 0.0001);
+        assert!((bottom_left[1] - 0.76).abs() < 0.0001);
+
+        assert_eq!(ctxt.width_per_height(), 4.0);
+    }
+
+    #[test]
+    fn scale_factor() {
+        let mut drawer = ();
+        let ctxt = ::draw().with_scale_factor(2.0).draw(400.0, 400.0, &mut drawer, None,
+                                                         false, false);
+        let ctxt = ctxt.margin_pixels(0.0, 0.0, 0.0, 100.0);
+
+        let top_left = ctxt.matrix() * [-1.0, 1.0, 1.0];
+        assert!((top_left[0] - 0.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn safe_area() {
+        let mut drawer = ();
+        let ctxt = ::draw().with_safe_area_insets(50.0, 0.0, 0.0, 0.0)
+                           .draw(100.0, 100.0, &mut drawer, None, false, false);
+
+        let top = ctxt.vertical_rescale(0.75, &VerticalAlignment::Top).safe_area();
+        let top_left = top.matrix() * [-1.0, 1.0, 1.0];
+        assert!((top_left[1] - 0.0).abs() < 0.0001);
+
+        let bottom = ctxt.vertical_rescale(0.25, &VerticalAlignment::Bottom);
+        assert_eq!(bottom.safe_area().matrix(), bottom.matrix());
+    }
+
+    #[test]
+    fn rect_pixels() {
+        let mut drawer = ();
+        let ctxt = ::draw().with_scale_factor(2.0).draw(400.0, 200.0, &mut drawer, None,
+                                                         false, false);
+        let ctxt = ctxt.anchor_pixels(&Alignment::bottom_right(), 10.0, 20.0, 30.0, 40.0);
+
+        let rect = ctxt.rect_pixels();
+        assert!((rect.x - 160.0).abs() < 0.001);
+        assert!((rect.y - 40.0).abs() < 0.001);
+        assert!((rect.width - 30.0).abs() < 0.001);
+        assert!((rect.height - 40.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn draw_deferred() {
+        let mut recorder = Recorder(Vec::new());
+
+        {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut recorder, None, false, false);
+            ctxt.draw_deferred(|ctxt| {
+                ctxt.draw_deferred(|ctxt| ctxt.draw().draw_image("c", &ctxt.matrix()));
+                ctxt.draw().draw_image("b", &ctxt.matrix());
+            });
+            ctxt.draw().draw_image("a", &ctxt.matrix());
+        }
+
+        assert_eq!(recorder.0, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn draw_deferred_panic() {
+        let mut recorder = Recorder(Vec::new());
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut recorder, None, false, false);
+            ctxt.draw_deferred(|_| panic!("deferred function called during unwinding"));
+            panic!("error while drawing");
+        }));
+
+        assert!(result.is_err());
+        assert!(recorder.0.is_empty());
+    }
+
+    #[test]
+    fn draw_deferred_layers() {
+        let mut recorder = Recorder(Vec::new());
+
+        {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut recorder, None, false, false);
+            ctxt.input_layer(2).draw_deferred(|ctxt| ctxt.draw().draw_image("c", &ctxt.matrix()));
+            ctxt.input_overlay().draw_deferred(|ctxt| ctxt.draw().draw_image("b", &ctxt.matrix()));
+            ctxt.draw().draw_image("a", &ctxt.matrix());
+        }
+
+        assert_eq!(recorder.0, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn hit_test_layers() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        for frame in 0 .. 2 {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some([0.0, 0.0]), false, false);
+            let below = ctxt.reserve_widget_id();
+            let above = ctxt.reserve_widget_id();
+
+            let below_hovered = ctxt.hit_test(&mut ui_state, &below);
+            let above_hovered = ctxt.input_overlay().hit_test(&mut ui_state, &above);
+
+            assert!(above_hovered);
+            assert!(ctxt.cursor_hovered_widget());
+            // During the first frame, nothing is known about the overlay yet.
+            assert_eq!(below_hovered, frame == 0);
+        }
+    }
+
+    #[test]
+    fn hit_test_overlap() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        for frame in 0 .. 2 {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some([0.1, 0.0]), false, false);
+            let first = ctxt.reserve_widget_id();
+            let second = ctxt.reserve_widget_id();
+            let third = ctxt.reserve_widget_id();
+
+            let first_hovered = ctxt.hit_test(&mut ui_state, &first);
+            let second_hovered = ctxt.horizontal_rescale(0.5, &HorizontalAlignment::Right)
+                                     .hit_test(&mut ui_state, &second);
+            let third_hovered = ctxt.horizontal_rescale(0.5, &HorizontalAlignment::Left)
+                                    .hit_test(&mut ui_state, &third);
+
+            assert_eq!(first_hovered, frame == 0);
+            assert!(second_hovered);
+            assert!(!third_hovered);
+        }
+    }
+
+    #[test]
+    fn pixel_snapping() {
+        let mut drawer = ();
+        let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, None, false, false);
+        let ctxt = ctxt.pixel_snapping(true).margin(0.004, 0.0, 0.0, 0.506);
+
+        let top_left = ctxt.matrix() * [-1.0, 1.0, 1.0];
+        assert!((top_left[0] - 0.02).abs() < 0.0001);
+        assert!((top_left[1] - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn multi_touch() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        let frames = [(TouchPhase::Started, -0.5, 0.5), (TouchPhase::Moved, 0.5, -0.5),
+                      (TouchPhase::Ended, 0.5, -0.5)];
+
+        for (frame, &(phase, x1, x2)) in frames.iter().enumerate() {
+            let mut input = Input::new(None);
+            input.touches = vec![
+                Touch { id: 1, position: [x1, 0.0], phase },
+                Touch { id: 2, position: [x2, 0.0], phase },
+            ];
+
+            let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+            let left = ctxt.horizontal_rescale(0.5, &HorizontalAlignment::Left);
+            let right = ctxt.horizontal_rescale(0.5, &HorizontalAlignment::Right);
+            let left_id = left.reserve_widget_id();
+            let right_id = right.reserve_widget_id();
+
+            let left_touches = left.captured_touches(&mut ui_state, &left_id);
+            let right_touches = right.captured_touches(&mut ui_state, &right_id);
+
+            // The touches stay captured by the same widget even after they have crossed.
+            assert_eq!(left_touches.len(), 1);
+            assert_eq!(left_touches[0].id, 1);
+            assert_eq!(right_touches.len(), 1);
+            assert_eq!(right_touches[0].id, 2);
+
+            if frame == 1 {
+                assert_eq!(left_touches[0].previous_position, [25.0, 50.0]);
+                assert_eq!(left_touches[0].position, [75.0, 50.0]);
+                assert_eq!(ui_state.touch_active_widget(2), Some(&right_id));
+            }
+        }
+
+        assert_eq!(ui_state.touch_active_widget(1), None);
+    }
+
+    #[test]
+    fn pinch_gesture() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        let mut gestures = Vec::new();
+        for &(phase, spread) in [(TouchPhase::Started, 0.2), (TouchPhase::Moved, 0.4)].iter() {
+            let mut input = Input::new(None);
+            input.touches = vec![
+                Touch { id: 1, position: [-spread, 0.0], phase },
+                Touch { id: 2, position: [spread, 0.0], phase },
+            ];
+
+            let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+            let id = ctxt.reserve_widget_id();
+            let touches = ctxt.captured_touches(&mut ui_state, &id);
+            gestures.push(Gesture::from_touches(&touches).unwrap());
+        }
+
+        assert_eq!(gestures[0].zoom, 1.0);
+        assert!((gestures[1].zoom - 2.0).abs() < 0.0001);
+        assert_eq!(gestures[1].center, [50.0, 50.0]);
+        assert_eq!(gestures[1].pan, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn directional_navigation() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        let moves = [None, Some(Navigation::Right), Some(Navigation::Right),
+                     Some(Navigation::Down), Some(Navigation::Left), Some(Navigation::Up)];
+        let mut focused = Vec::new();
+
+        for navigation in moves.iter() {
+            let mut input = Input::new(None);
+            input.navigation = navigation.iter().cloned().collect();
+            let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+
+            // A grid of two rows of two widgets.
+            let mut focused_cell = None;
+            for (row_num, row) in ctxt.vertical_split(2).enumerate() {
+                for (col_num, cell) in row.horizontal_split(2).enumerate() {
+                    let id = cell.reserve_widget_id();
+                    if cell.focusable(&mut ui_state, &id) {
+                        focused_cell = Some((row_num, col_num));
+                    }
+                }
+            }
+            focused.push(focused_cell);
+        }
+
+        assert_eq!(focused, vec![None, Some((0, 0)), Some((0, 1)), Some((1, 1)), Some((1, 0)),
+                                 Some((0, 0))]);
+    }
+
+    #[test]
+    fn cursor_icon() {
+        let mut drawer = ();
+        let shared = ::draw();
+
+        {
+            let ctxt = shared.draw(100.0, 100.0, &mut drawer, None, false, false);
+            ctxt.input_overlay().request_cursor_icon(CursorIcon::Grabbing);
+            ctxt.request_cursor_icon(CursorIcon::Hand);
+        }
+
+        assert_eq!(shared.cursor_icon(), CursorIcon::Grabbing);
+        assert_eq!(::draw().cursor_icon(), CursorIcon::Arrow);
+    }
+
+    #[test]
+    fn widget_data() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        for frame in 0 .. 4 {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, None, false, false);
+            let first = ctxt.reserve_widget_id();
+            let second = ctxt.reserve_widget_id();
+
+            *ctxt.widget_data(&mut ui_state, &first, || 0u32) += 1;
+            assert_eq!(ctxt.widget_data(&mut ui_state, &first, String::new).len(), 0);
+
+            // The second widget isn't drawn during the third frame.
+            if frame != 2 {
+                *ctxt.widget_data(&mut ui_state, &second, || 0u32) += 1;
+            }
+
+            assert_eq!(ui_state.widget_data::<u32>(&first), Some(&(frame + 1)));
+            // Its value is dropped at the start of the fourth frame, and a new one is created.
+            let expected = match frame { 2 => Some(2), 3 => Some(1), _ => Some(frame + 1) };
+            assert_eq!(ui_state.widget_data::<u32>(&second).cloned(), expected);
+        }
+    }
+}
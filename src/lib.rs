@@ -114,32 +114,236 @@
 //! }
 //! ```
 //!
+
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
 pub use draw::DrawImage;
 pub use draw::DrawText;
 pub use draw::GlyphInfos;
 pub use id::WidgetId;
+pub use input::ButtonState;
+pub use input::CapturedTouch;
+pub use input::CursorIcon;
+pub use input::Gesture;
+pub use input::Input;
+pub use input::Key;
+pub use input::Modifiers;
+pub use input::MouseButton;
+pub use input::Navigation;
+pub use input::Touch;
+pub use input::TouchPhase;
 pub use layout::draw;
 pub use layout::Alignment;
 pub use layout::DrawContext;
 pub use layout::SharedDrawContext;
 pub use layout::HorizontalAlignment;
+pub use layout::Rect;
 pub use layout::VerticalAlignment;
 pub use matrix::Matrix;
 
+use std::any::Any;
+use std::fmt::Debug;
+use std::time::SystemTime;
+
 mod draw;
 mod id;
+mod input;
 mod layout;
 mod matrix;
+mod storage;
 
 pub mod animations;
 pub mod widgets;
 
 /// Contains some persistent info about the UI.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+///
+/// The state also holds private data gathered during the previous frame, like the areas of the
+/// widgets, so it must be built with `UiState::default()`. It is only `PartialEq`, as this data
+/// contains floating-point coordinates.
+///
+/// With the `serde` feature, the state can be serialized. Only the public fields are saved, the
+/// rest is transient and is restored to its default value.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UiState {
     /// Identifier of the widget that is currently active.
     ///
     /// For example if you maintain the left button of the mouse, the element under will be active.
     /// If you then move your mouse somewhere else, the active element doesn't change.
     pub active_widget: Option<WidgetId>,
+
+    /// Identifier of the widget whose popup is currently open, for example a dropdown.
+    ///
+    /// Only one popup can be open at a time. Set this to `None` to close the popup.
+    pub open_popup: Option<WidgetId>,
+
+    /// Identifier of the widget that has the keyboard focus, if any.
+    ///
+    /// Widgets that react to the keyboard, like lists, only do so when they have the focus. They
+    /// usually take the focus when they are clicked.
+    ///
+    /// The focus can also be moved between the widgets that call `DrawContext::focusable` with a
+    /// `Navigation` input.
+    pub focused_widget: Option<WidgetId>,
+
+    /// Mouse button that has been pressed over a widget and not released yet.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    press: Option<layout::Press>,
+
+    /// Last widget that has been clicked with the left button, and the moment of the click. Used
+    /// to detect double clicks.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_click: Option<(WidgetId, SystemTime)>,
+
+    /// Touches that are captured by a widget.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    touch_captures: Vec<layout::TouchCapture>,
+
+    /// Drag-and-drop operation in progress. See `widgets::drag_drop`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    drag: Option<layout::Drag>,
+
+    /// Number of the frame during which the state was last used.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frame: usize,
+
+    /// Areas registered with `DrawContext::hit_test` during the previous frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hit_boxes: Vec<layout::HitBox>,
+
+    /// Areas registered with `DrawContext::hit_test` during the current frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    next_hit_boxes: Vec<layout::HitBox>,
+
+    /// Areas registered with `DrawContext::focusable` during the previous frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    focus_boxes: Vec<layout::FocusBox>,
+
+    /// Areas registered with `DrawContext::focusable` during the current frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    next_focus_boxes: Vec<layout::FocusBox>,
+
+    /// Number of the last frame whose navigation inputs have been handled.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    navigation_frame: usize,
+
+    /// Values stored by the widgets with `DrawContext::widget_data`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    storage: storage::Storage,
+}
+
+impl UiState {
+    /// Returns the identifier of the widget that has captured the touch with the given
+    /// identifier, if any.
+    ///
+    /// This is the equivalent of `active_widget` for each point of contact with a touch screen.
+    /// See `DrawContext::captured_touches`.
+    pub fn touch_active_widget(&self, touch: u64) -> Option<&WidgetId> {
+        self.touch_captures.iter().find(|c| c.touch == touch).map(|c| &c.widget)
+    }
+
+    /// Returns true if something is being dragged with `widgets::drag_drop`.
+    ///
+    /// This is false between the moment when the button is pressed over a drag source and the
+    /// moment when the cursor has moved far enough.
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.drag.as_ref().map(|d| d.moving && !d.released).unwrap_or(false)
+    }
+
+    /// Returns the value of type `T` stored for the given widget with `DrawContext::widget_data`,
+    /// if any.
+    #[inline]
+    pub fn widget_data<T>(&self, widget_id: &WidgetId) -> Option<&T>
+        where T: Any + Clone + Debug + PartialEq + Send + Sync
+    {
+        self.storage.get(widget_id)
+    }
+
+    /// Removes the value of type `T` stored for the given widget with `DrawContext::widget_data`,
+    /// and returns it.
+    #[inline]
+    pub fn remove_widget_data<T>(&mut self, widget_id: &WidgetId) -> Option<T>
+        where T: Any + Clone + Debug + PartialEq + Send + Sync
+    {
+        self.storage.remove(widget_id)
+    }
+
+    /// Cancels the drag-and-drop operation in progress, if any.
+    #[inline]
+    pub fn cancel_drag(&mut self) {
+        self.drag = None;
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde_json;
+
+    use Alignment;
+    use HorizontalAlignment;
+    use Matrix;
+    use UiState;
+    use VerticalAlignment;
+    use WidgetId;
+
+    #[test]
+    fn serde_round_trip() {
+        let matrix = Matrix::translate(0.5, -0.25) * Matrix::scale(2.0);
+        let json = serde_json::to_string(&matrix).unwrap();
+        assert_eq!(serde_json::from_str::<Matrix>(&json).unwrap(), matrix);
+
+        let alignment = Alignment::bottom_right();
+        let json = serde_json::to_string(&alignment).unwrap();
+        assert_eq!(serde_json::from_str::<Alignment>(&json).unwrap(), alignment);
+
+        let json = serde_json::to_string(&HorizontalAlignment::Right).unwrap();
+        assert_eq!(serde_json::from_str::<HorizontalAlignment>(&json).unwrap(),
+                   HorizontalAlignment::Right);
+
+        let json = serde_json::to_string(&VerticalAlignment::Top).unwrap();
+        assert_eq!(serde_json::from_str::<VerticalAlignment>(&json).unwrap(),
+                   VerticalAlignment::Top);
+    }
+
+    #[test]
+    fn serde_ui_state() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        let widget = {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some([0.0, 0.0]), true, false);
+            let widget = ctxt.reserve_widget_id();
+            assert!(ctxt.hit_test(&mut ui_state, &widget));
+            *ctxt.widget_data(&mut ui_state, &widget, || 0u32) = 7;
+            ui_state.active_widget = Some(widget.clone());
+            ui_state.focused_widget = Some(widget.clone());
+            ui_state.open_popup = Some(WidgetId::from(42));
+            widget
+        };
+
+        let json = serde_json::to_string(&ui_state).unwrap();
+        let mut restored: UiState = serde_json::from_str(&json).unwrap();
+
+        // Only the public fields are restored. The data gathered during the frame is reset.
+        assert_eq!(restored, UiState {
+            active_widget: Some(widget.clone()),
+            open_popup: Some(WidgetId::from(42)),
+            focused_widget: Some(widget.clone()),
+            .. UiState::default()
+        });
+        assert!(ui_state.frame != 0 && restored.frame == 0);
+        assert!(restored.next_hit_boxes.is_empty());
+        assert_eq!(restored.widget_data::<u32>(&widget), None);
+
+        // The restored state can be used to draw the next frame.
+        let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some([0.0, 0.0]), false, false);
+        let widget = ctxt.reserve_widget_id();
+        assert!(ctxt.hit_test(&mut restored, &widget));
+        assert_eq!(*ctxt.widget_data(&mut restored, &widget, || 1u32), 1);
+    }
 }
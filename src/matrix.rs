@@ -9,6 +9,7 @@ use std::ops;
 
 /// A 2x3 matrix. The data is stored in column-major.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Matrix(pub [[f32; 2]; 3]);
 
 impl Matrix {
@@ -80,6 +81,7 @@ impl Matrix {
     /// Builds the matrix's invert.
     ///
     /// Returns `None` if the determinant is zero, infinite or NaN.
+    #[allow(clippy::eq_op)]
     pub fn invert(&self) -> Option<[[f32; 3]; 3]> {
         let me = self.0;
         let det = me[0][0] * me[1][1] - me[1][0] * me[0][1];
@@ -140,6 +142,7 @@ impl ops::Mul<[f32; 3]> for Matrix {
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<[[f32; 3]; 3]> for Matrix {
     #[inline]
     fn into(self) -> [[f32; 3]; 3] {
@@ -153,6 +156,7 @@ impl Into<[[f32; 3]; 3]> for Matrix {
     }
 }
 
+#[allow(clippy::from_over_into)]
 impl Into<[[f32; 4]; 4]> for Matrix {
     #[inline]
     fn into(self) -> [[f32; 4]; 4] {
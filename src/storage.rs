@@ -0,0 +1,114 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+
+use WidgetId;
+
+/// Values stored by the widgets in the `UiState`. See `DrawContext::widget_data`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Storage {
+    entries: HashMap<(WidgetId, TypeId), Entry>,
+}
+
+impl Storage {
+    /// Returns the value of type `T` stored for the given widget, if any.
+    pub fn get<T>(&self, widget_id: &WidgetId) -> Option<&T>
+        where T: Any + Clone + fmt::Debug + PartialEq + Send + Sync
+    {
+        self.entries.get(&(widget_id.clone(), TypeId::of::<T>()))
+                    .and_then(|e| e.value.as_any().downcast_ref())
+    }
+
+    /// Returns the value of type `T` stored for the given widget, inserting the result of
+    /// `default` if there is none, and marks it as used during the given frame.
+    pub fn get_or_insert_with<T, F>(&mut self, widget_id: &WidgetId, frame: usize, default: F)
+                                    -> &mut T
+        where T: Any + Clone + fmt::Debug + PartialEq + Send + Sync, F: FnOnce() -> T
+    {
+        let entry = self.entries.entry((widget_id.clone(), TypeId::of::<T>())).or_insert_with(|| {
+            Entry { frame, value: Box::new(default()) }
+        });
+        entry.frame = frame;
+        entry.value.as_any_mut().downcast_mut().unwrap()
+    }
+
+    /// Removes the value of type `T` stored for the given widget, and returns it.
+    pub fn remove<T>(&mut self, widget_id: &WidgetId) -> Option<T>
+        where T: Any + Clone + fmt::Debug + PartialEq + Send + Sync
+    {
+        self.entries.remove(&(widget_id.clone(), TypeId::of::<T>()))
+                    .and_then(|e| e.value.as_any().downcast_ref().cloned())
+    }
+
+    /// Removes the values that haven't been used during the given frame.
+    pub fn collect(&mut self, frame: usize) {
+        self.entries.retain(|_, e| e.frame == frame);
+    }
+}
+
+/// A value in the storage.
+struct Entry {
+    /// Last frame during which the value has been used.
+    frame: usize,
+    value: Box<dyn Value>,
+}
+
+impl Clone for Entry {
+    fn clone(&self) -> Entry {
+        Entry {
+            frame: self.frame,
+            value: self.value.clone_box(),
+        }
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt_debug(f)
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Entry) -> bool {
+        self.frame == other.frame && self.value.eq_box(&*other.value)
+    }
+}
+
+/// Object-safe version of the traits required for the values of the storage.
+trait Value: Any + Send + Sync {
+    fn clone_box(&self) -> Box<dyn Value>;
+    fn eq_box(&self, other: &dyn Value) -> bool;
+    fn fmt_debug(&self, f: &mut fmt::Formatter) -> fmt::Result;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T> Value for T where T: Any + Clone + fmt::Debug + PartialEq + Send + Sync {
+    fn clone_box(&self) -> Box<dyn Value> {
+        Box::new(self.clone())
+    }
+
+    fn eq_box(&self, other: &dyn Value) -> bool {
+        other.as_any().downcast_ref::<T>().map(|o| self == o).unwrap_or(false)
+    }
+
+    fn fmt_debug(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -44,6 +44,7 @@ pub fn draw<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, empty: &
 /// # Panic
 ///
 /// Panicks if `progress` is not between 0.0 and 1.0.
+#[allow(clippy::approx_constant)]
 pub fn stretch<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, empty: &I,
                                             full: &I, progress: f32)
 {
@@ -0,0 +1,261 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Drag-and-drop lets the user move some data from one widget to another with the cursor.
+//!
+//! A context becomes a drag source by calling `source` with the data that is dragged from it,
+//! called the payload. When the button is pressed over the source and the cursor moves, the
+//! payload is stored in the `UiState` until the button is released.
+//!
+//! Meanwhile, a drop target calls `target` with the type of payload it accepts. It is told when
+//! a payload of this type is hovering it, and when it's dropped on it. Payloads of other types
+//! are ignored, which makes it possible to have several kinds of drag-and-drop in the same UI.
+//!
+//! The `preview` function draws an image that follows the cursor during the drag, on the layer
+//! above the one of the context.
+//!
+//! ```
+//! # fn example<D>(ctxt: &immi::DrawContext<D>, ui_state: &mut immi::UiState)
+//! #     where D: immi::DrawImage<str>
+//! # {
+//! let slots = ctxt.horizontal_split(2).collect::<Vec<_>>();
+//!
+//! // The item in the first slot can be dragged...
+//! immi::widgets::drag_drop::source(&slots[0], ui_state, 42u32);
+//!
+//! // ... and dropped on the second slot.
+//! if let Some(state) = immi::widgets::drag_drop::target::<_, u32>(&slots[1], ui_state) {
+//!     if state.dropped() {
+//!         println!("Item {} moved", state.payload());
+//!     }
+//! }
+//!
+//! immi::widgets::drag_drop::preview(ctxt, ui_state, "item");
+//! # }
+//! ```
+
+use std::any::Any;
+use std::borrow::Borrow;
+use std::sync::Arc;
+
+use Alignment;
+use CursorIcon;
+use DrawImage;
+use DrawContext;
+use UiState;
+
+use layout::Drag;
+use widgets::image;
+
+/// Distance in logical pixels that the cursor must move after the button has been pressed over
+/// a source before the drag starts. This leaves some room for clicks.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// What is happening to a drop target.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetState<T> {
+    /// A payload is being dragged over the target.
+    Hovered(Arc<T>),
+    /// A payload has been dropped on the target during this frame.
+    Dropped(Arc<T>),
+}
+
+impl<T> TargetState<T> {
+    /// Returns the payload that is hovering or has been dropped.
+    #[inline]
+    pub fn payload(&self) -> &T {
+        match *self {
+            TargetState::Hovered(ref p) => p,
+            TargetState::Dropped(ref p) => p,
+        }
+    }
+
+    /// Returns `true` if equal to `Dropped`.
+    #[inline]
+    pub fn dropped(&self) -> bool {
+        matches!(*self, TargetState::Dropped(_))
+    }
+}
+
+/// Makes the context a source of drag-and-drop with the given payload.
+///
+/// The area of the context is registered with `DrawContext::hit_test`. Returns `true` if the
+/// payload of this source is currently being dragged, for example in order to draw the source
+/// differently.
+pub fn source<D: ?Sized, T>(draw: &DrawContext<D>, ui_state: &mut UiState, payload: T) -> bool
+    where T: Any + Send + Sync
+{
+    let widget_id = draw.reserve_widget_id();
+    let hovered = draw.hit_test(ui_state, &widget_id);
+    update(draw, ui_state);
+
+    if hovered && draw.cursor_was_pressed() && ui_state.drag.is_none() {
+        if let Some(cursor) = draw.cursor_position_pixels() {
+            let rect = draw.rect_pixels();
+            ui_state.drag = Some(Drag {
+                source: widget_id.clone(),
+                payload: Arc::new(payload),
+                origin: cursor,
+                grab_offset: [cursor[0] - rect.x, cursor[1] - rect.y],
+                size: [rect.width, rect.height],
+                moving: false,
+                released: false,
+            });
+        }
+    }
+
+    match ui_state.drag {
+        Some(ref drag) => drag.source == widget_id && drag.moving && !drag.released,
+        None => false,
+    }
+}
+
+/// Makes the context a drop target for payloads of type `T`.
+///
+/// Returns `None` if no payload of this type is being dragged over the context. The area of the
+/// context isn't registered, so that the widgets drawn in the target can still be hovered.
+pub fn target<D: ?Sized, T>(draw: &DrawContext<D>, ui_state: &mut UiState)
+                            -> Option<TargetState<T>>
+    where T: Any + Send + Sync
+{
+    let hovered = draw.is_cursor_hovering_uncovered(ui_state);
+    update(draw, ui_state);
+
+    if !hovered {
+        return None;
+    }
+
+    let drag = match ui_state.drag {
+        Some(ref drag) if drag.moving => drag,
+        _ => return None,
+    };
+
+    let payload = match drag.payload.clone().downcast::<T>() {
+        Ok(p) => p,
+        Err(_) => return None,
+    };
+
+    if drag.released {
+        Some(TargetState::Dropped(payload))
+    } else {
+        Some(TargetState::Hovered(payload))
+    }
+}
+
+/// Returns the payload that is being dragged, if any and if it's of type `T`.
+pub fn payload<T>(ui_state: &UiState) -> Option<Arc<T>>
+    where T: Any + Send + Sync
+{
+    match ui_state.drag {
+        Some(ref drag) if drag.moving && !drag.released => drag.payload.clone().downcast().ok(),
+        _ => None,
+    }
+}
+
+/// Draws an image that follows the cursor while something is being dragged.
+///
+/// The image has the dimensions of the source and keeps the same position relative to the
+/// cursor as when the drag started.
+pub fn preview<'b, D, I>(draw: &DrawContext<'b, D>, ui_state: &mut UiState, image: &I)
+    where D: ?Sized + DrawImage<I> + 'b, I: ?Sized + ToOwned, I::Owned: 'b
+{
+    let area = match preview_area(draw, ui_state) {
+        Some(a) => a,
+        None => return,
+    };
+
+    let image = image.to_owned();
+    area.draw_deferred(move |area| {
+        image::stretch(area, image.borrow());
+    });
+}
+
+/// Returns the context where the preview of the drag must be drawn, or `None` if nothing is
+/// being dragged.
+///
+/// The returned context belongs to the layer above the one of `draw`, but you have to use
+/// `draw_deferred` for the preview to be drawn on top of the other widgets. This function also
+/// requests the `Grabbing` cursor icon on this layer.
+pub fn preview_area<'b, D: ?Sized>(draw: &DrawContext<'b, D>, ui_state: &mut UiState)
+                                   -> Option<DrawContext<'b, D>>
+{
+    update(draw, ui_state);
+
+    let drag = match ui_state.drag {
+        Some(ref drag) if drag.moving && !drag.released => drag,
+        _ => return None,
+    };
+
+    let cursor = draw.cursor_position_pixels()?;
+
+    let area = draw.viewport().absolute_pixels(cursor[0] - drag.grab_offset[0],
+                                               cursor[1] - drag.grab_offset[1], drag.size[0],
+                                               drag.size[1], &Alignment::top_left());
+    let area = area.input_overlay();
+    area.request_cursor_icon(CursorIcon::Grabbing);
+    Some(area)
+}
+
+/// Starts moving the drag once the cursor is far enough from where the button was pressed, and
+/// marks it as released when the button is released.
+fn update<D: ?Sized>(draw: &DrawContext<D>, ui_state: &mut UiState) {
+    let drag = match ui_state.drag {
+        Some(ref mut drag) => drag,
+        None => return,
+    };
+
+    if !drag.moving {
+        if let Some(cursor) = draw.cursor_position_pixels() {
+            let dx = cursor[0] - drag.origin[0];
+            let dy = cursor[1] - drag.origin[1];
+            if dx * dx + dy * dy > DRAG_THRESHOLD * DRAG_THRESHOLD {
+                drag.moving = true;
+            }
+        }
+    }
+
+    if draw.cursor_was_released() {
+        drag.released = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use HorizontalAlignment;
+    use UiState;
+
+    use widgets::drag_drop;
+    use widgets::tests::cursor_gl;
+
+    #[test]
+    fn drag_and_drop() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        // Dragging from the left half of the viewport to the right half.
+        let frames = [([25.0, 50.0], true, false), ([75.0, 50.0], false, false),
+                      ([75.0, 50.0], false, true), ([75.0, 50.0], false, false)];
+        let mut results = Vec::new();
+
+        for &(cursor, pressed, released) in frames.iter() {
+            let cursor = cursor_gl([100.0, 100.0], cursor);
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some(cursor), pressed, released);
+            let source = ctxt.horizontal_rescale(0.5, &HorizontalAlignment::Left);
+            let target = ctxt.horizontal_rescale(0.5, &HorizontalAlignment::Right);
+
+            let dragging = drag_drop::source(&source, &mut ui_state, 7u32);
+            let other_type = drag_drop::target::<_, String>(&target, &mut ui_state);
+            let state = drag_drop::target::<_, u32>(&target, &mut ui_state);
+            assert!(other_type.is_none());
+            results.push((dragging, state.map(|s| (*s.payload(), s.dropped()))));
+        }
+
+        assert_eq!(results, vec![(false, None), (true, Some((7, false))), (false, Some((7, true))),
+                                 (false, None)]);
+        assert!(!ui_state.is_dragging());
+    }
+}
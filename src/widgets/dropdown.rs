@@ -0,0 +1,204 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A dropdown is a button that shows the current choice, and that opens a list of options when
+//! clicked.
+//!
+//! The list of options is drawn below the button, on the layer above the one of the context. It
+//! closes when an option is clicked, when the user clicks outside of it, or when the escape key
+//! is pressed.
+//!
+//! The index of the selected option is stored in a variable owned by the caller. Whether the
+//! list is open is stored in the `UiState`.
+//!
+//! Since the list is drawn after everything else with `DrawContext::draw_deferred`, the images,
+//! the text style and the options are cloned.
+
+use std::borrow::Borrow;
+
+use Alignment;
+use DrawImage;
+use DrawText;
+use DrawContext;
+use HorizontalAlignment;
+use Key;
+use Navigation;
+use UiState;
+
+use widgets::image;
+use widgets::image_button;
+use widgets::label;
+
+/// Draws a dropdown over the whole area of the context.
+///
+/// The button uses the `normal_image`, `hovered_image` and `active_image` and shows the text of
+/// the selected option. When the list is open, it uses the same width as the button and each
+/// option has the same height as the button. The `list_image` is drawn behind the options, and
+/// the `highlight_image` behind the option that is hovered.
+///
+/// Returns `true` if the selection has changed.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<'b, D, I, T, S>(draw: &DrawContext<'b, D>, ui_state: &mut UiState,
+                            selection: &mut usize, options: &[S], normal_image: &I,
+                            hovered_image: &I, active_image: &I, list_image: &I,
+                            highlight_image: &I, text_style: &T) -> bool
+    where D: ?Sized + DrawImage<I> + DrawText<T> + 'b, I: ?Sized + ToOwned, I::Owned: 'b,
+          T: ?Sized + ToOwned, T::Owned: 'b, S: AsRef<str>
+{
+    let draw = draw.animation_stop();
+    let widget_id = draw.reserve_widget_id();
+    let mut changed = false;
+
+    // The button.
+    let was_open = ui_state.open_popup == Some(widget_id.clone());
+    if image_button::stretch(&draw, ui_state, normal_image, hovered_image, active_image).clicked() {
+        ui_state.open_popup = if was_open { None } else { Some(widget_id.clone()) };
+    }
+
+    let text = options.get(*selection).map(|o| o.as_ref()).unwrap_or("");
+    label::flow(&draw.uniform_margin(0.1, 0.1, 0.1, 0.1), text_style, text,
+                &HorizontalAlignment::Left);
+
+    if ui_state.open_popup != Some(widget_id.clone()) || options.is_empty() {
+        return false;
+    }
+
+    // The list of options, drawn on top of the current layer.
+    let list = draw.absolute(0.0, 1.0, 1.0, options.len() as f32, &Alignment::top_left())
+                   .input_overlay();
+    let list_hovered = list.hit_test(ui_state, &widget_id);
+    let mut highlighted = None;
+
+    for (num, row) in list.vertical_split(options.len()).enumerate() {
+        if !list_hovered || !row.is_cursor_hovering() {
+            continue;
+        }
+
+        highlighted = Some(num);
+
+        if row.cursor_was_pressed() {
+            ui_state.active_widget = Some(widget_id.clone());
+
+        } else if row.cursor_was_released() && ui_state.active_widget == Some(widget_id.clone()) {
+            ui_state.active_widget = None;
+            ui_state.open_popup = None;
+            changed = *selection != num;
+            *selection = num;
+        }
+    }
+
+    let clicked_outside = draw.cursor_was_pressed() && !list_hovered &&
+                          !draw.is_cursor_hovering();
+    if clicked_outside || draw.key_was_pressed(Key::Escape) ||
+       draw.navigation_was_pressed(Navigation::Back)
+    {
+        ui_state.open_popup = None;
+    }
+
+    // The list isn't drawn during the frame when it closes.
+    if ui_state.open_popup != Some(widget_id.clone()) {
+        return changed;
+    }
+
+    let list_image = list_image.to_owned();
+    let highlight_image = highlight_image.to_owned();
+    let text_style = text_style.to_owned();
+    let options: Vec<String> = options.iter().map(|o| o.as_ref().to_owned()).collect();
+
+    list.draw_deferred(move |list| {
+        image::stretch(list, list_image.borrow());
+
+        for (num, (row, option)) in list.vertical_split(options.len()).zip(options.iter())
+                                        .enumerate()
+        {
+            if highlighted == Some(num) {
+                image::stretch(&row, highlight_image.borrow());
+            }
+
+            label::flow(&row.uniform_margin(0.1, 0.1, 0.1, 0.1), text_style.borrow(), option,
+                        &HorizontalAlignment::Left);
+        }
+    });
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use ButtonState;
+    use Input;
+    use Key;
+    use UiState;
+    use VerticalAlignment;
+
+    use widgets::dropdown;
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+
+    /// Draws a frame with a dropdown of three options at the top of a viewport of 100x100
+    /// pixels. The button is 20 pixels high, and so is each option of the list. `y` is the
+    /// position of the cursor from the top, in pixels. Returns whether the selection changed, and
+    /// whether the list has been drawn.
+    fn frame(ui_state: &mut UiState, selection: &mut usize, y: f32, pressed: bool,
+             released: bool, keys: &[Key]) -> (bool, bool)
+    {
+        let mut drawer = Recorder::default();
+
+        let changed = {
+            let mut input = Input::new(Some(cursor_gl([100.0, 100.0], [50.0, y])));
+            input.left = ButtonState { down: pressed, pressed, released };
+            input.keys = keys.to_vec();
+            let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+            let ctxt = ctxt.vertical_rescale(0.2, &VerticalAlignment::Top);
+
+            dropdown::draw(&ctxt, ui_state, selection, &["a", "b", "c"], "normal", "hovered",
+                           "active", "list", "highlight", "text")
+        };
+
+        (changed, drawer.images.iter().any(|i| i == "list"))
+    }
+
+    fn click(ui_state: &mut UiState, selection: &mut usize, y: f32) -> (bool, bool) {
+        frame(ui_state, selection, y, true, false, &[]);
+        frame(ui_state, selection, y, false, true, &[])
+    }
+
+    #[test]
+    fn select_option() {
+        let mut ui_state = UiState::default();
+        let mut selection = 0;
+
+        assert_eq!(click(&mut ui_state, &mut selection, 10.0), (false, true));
+        assert!(ui_state.open_popup.is_some());
+
+        // Clicking the third option.
+        assert_eq!(click(&mut ui_state, &mut selection, 70.0), (true, false));
+        assert_eq!(selection, 2);
+        assert_eq!(ui_state.open_popup, None);
+    }
+
+    #[test]
+    fn close_without_selecting() {
+        let mut ui_state = UiState::default();
+        let mut selection = 1;
+
+        // Escape closes the list.
+        click(&mut ui_state, &mut selection, 10.0);
+        let (changed, list_drawn) = frame(&mut ui_state, &mut selection, 50.0, false, false,
+                                          &[Key::Escape]);
+        assert!(!changed && !list_drawn);
+        assert_eq!(ui_state.open_popup, None);
+
+        // So does clicking outside of the button and of the list.
+        click(&mut ui_state, &mut selection, 10.0);
+        let (changed, list_drawn) = frame(&mut ui_state, &mut selection, 95.0, true, false, &[]);
+        assert!(!changed && !list_drawn);
+        assert_eq!(ui_state.open_popup, None);
+
+        assert_eq!(selection, 1);
+    }
+}
@@ -24,6 +24,7 @@ pub fn draw<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, image_na
 }
 
 /// Stretches the image if necessary so that it corresponds to the context's area, then draws it.
+#[allow(clippy::collapsible_if)]
 pub fn stretch<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, image_name: &I) {
     if !draw.cursor_hovered_widget() {
         if draw.is_cursor_hovering() {
@@ -23,12 +23,31 @@ use Alignment;
 use DrawImage;
 use DrawContext;
 
+/// Same as `draw`, except that the width of the left border is expressed in logical pixels
+/// instead of a percentage of the context's width.
+///
+/// # Panic
+///
+/// - Panics if `top_percent + bottom_percent > 1.0` or `left_percent + right_percent > 1.0`.
+///
+#[inline]
+pub fn draw_pixels<D, I>(draw: &DrawContext<D>, left_border_pixels: f32, image_name: &I,
+                         top_percent: f32, right_percent: f32, bottom_percent: f32,
+                         left_percent: f32)
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    let left_border_percent = left_border_pixels / draw.width_pixels();
+    self::draw(draw, left_border_percent, image_name, top_percent, right_percent, bottom_percent,
+               left_percent);
+}
+
 /// Draws a 9-parts image.
 ///
 /// # Panic
 ///
 /// - Panics if `top_percent + bottom_percent > 1.0` or `left_percent + right_percent > 1.0`.
 ///
+#[allow(clippy::collapsible_if)]
 pub fn draw<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, left_border_percent: f32,
                                                  image_name: &I, top_percent: f32, right_percent: f32,
                                                  bottom_percent: f32, left_percent: f32)
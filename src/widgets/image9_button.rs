@@ -11,46 +11,75 @@ use DrawImage;
 use DrawContext;
 use UiState;
 
+use widgets;
 use widgets::Interaction;
+use widgets::Interactions;
+use widgets::Look;
 use widgets::image9;
 
 /// Same as `image9::draw`, except that the image is clickable. You can specify different images
 /// for when the button is non-hovered, hovered, or active. 
+///
+/// If the context is disabled, the normal image is drawn. See `DrawContext::disabled`.
+#[allow(clippy::too_many_arguments)]
 pub fn draw<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, ui_state: &mut UiState,
                                                  left_border_percent: f32, normal_image: &I,
                                                  hovered_image: &I, active_image: &I,
                                                  top_percent: f32, right_percent: f32, bottom_percent: f32,
                                                  left_percent: f32) -> Interaction
 {
-    let widget_id = draw.reserve_widget_id();
-
-    if draw.is_cursor_hovering() {
-        if Some(widget_id.clone()) == ui_state.active_widget {
-            image9::draw(draw, left_border_percent, active_image, top_percent, right_percent,
-                         bottom_percent, left_percent);
-
-            if draw.cursor_was_released() {
-                ui_state.active_widget = None;
-                Interaction::Clicked
-            } else {
-                Interaction::None
-            }
-
-        } else if draw.cursor_was_pressed() {
-            image9::draw(draw, left_border_percent, active_image, top_percent, right_percent,
-                         bottom_percent, left_percent);
-            ui_state.active_widget = Some(widget_id.clone());
-            Interaction::None
-
-        } else {
-            image9::draw(draw, left_border_percent, hovered_image, top_percent, right_percent,
-                         bottom_percent, left_percent);
-            Interaction::None
-        }
-
-    } else {
-        image9::draw(draw, left_border_percent, normal_image, top_percent, right_percent,
-                     bottom_percent, left_percent);
-        Interaction::None
-    }
+    draw_interactions(draw, ui_state, left_border_percent, normal_image, hovered_image,
+                      active_image, top_percent, right_percent, bottom_percent, left_percent)
+        .interaction()
+}
+
+/// Same as `draw`, except that `disabled_image` is drawn if the context is disabled.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_with_disabled<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState,
+                                left_border_percent: f32, normal_image: &I, hovered_image: &I,
+                                active_image: &I, disabled_image: &I, top_percent: f32,
+                                right_percent: f32, bottom_percent: f32, left_percent: f32)
+                                -> Interaction
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    draw_with_disabled_interactions(draw, ui_state, left_border_percent, normal_image,
+                                    hovered_image, active_image, disabled_image, top_percent,
+                                    right_percent, bottom_percent, left_percent).interaction()
+}
+
+/// Same as `draw`, except that it returns the detailed `Interactions`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_interactions<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState,
+                               left_border_percent: f32, normal_image: &I, hovered_image: &I,
+                               active_image: &I, top_percent: f32, right_percent: f32,
+                               bottom_percent: f32, left_percent: f32) -> Interactions
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    draw_with_disabled_interactions(draw, ui_state, left_border_percent, normal_image,
+                                    hovered_image, active_image, normal_image, top_percent,
+                                    right_percent, bottom_percent, left_percent)
+}
+
+/// Same as `draw_with_disabled`, except that it returns the detailed `Interactions`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_with_disabled_interactions<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState,
+                                             left_border_percent: f32, normal_image: &I,
+                                             hovered_image: &I, active_image: &I,
+                                             disabled_image: &I, top_percent: f32,
+                                             right_percent: f32, bottom_percent: f32,
+                                             left_percent: f32) -> Interactions
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    let (interactions, look) = widgets::button(draw, ui_state);
+
+    let image = match look {
+        Look::Normal => normal_image,
+        Look::Hovered => hovered_image,
+        Look::Active => active_image,
+        Look::Disabled => disabled_image,
+    };
+    image9::draw(draw, left_border_percent, image, top_percent, right_percent, bottom_percent,
+                 left_percent);
+
+    interactions
 }
@@ -8,17 +8,21 @@
 //! Image buttons are images that are clickable.
 //!
 //! You can specify different images for when the button is in a normal state, a hovered state,
-//! or an active state.
+//! or an active state. The `_with_disabled` variants also take an image for when the context is
+//! disabled. Otherwise disabled buttons use the normal image. See `DrawContext::disabled`.
 //!
-//! All the functions in this module return an `Interaction` object that indicates whether they
-//! were clicked. 
+//! The functions in this module return whether the button has been clicked. The `_interactions`
+//! variants return the detailed `Interactions` instead. See `widgets::interact`.
 
 use Alignment;
 use DrawImage;
 use DrawContext;
 use UiState;
 
+use widgets;
 use widgets::Interaction;
+use widgets::Interactions;
+use widgets::Look;
 
 /// Same as `image::draw`, except that the image is clickable.
 pub fn draw<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, ui_state: &mut UiState,
@@ -26,10 +30,18 @@ pub fn draw<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, ui_state
                                                  active_image: &I, alignment: &Alignment)
                                                  -> Interaction
 {
-    let draw = draw.animation_stop();
-    let ratio = draw.draw().get_image_width_per_height(normal_image);
-    stretch(&draw.enforce_aspect_ratio_downscale(ratio, alignment), ui_state, normal_image,
-            hovered_image, active_image)
+    draw_interactions(draw, ui_state, normal_image, hovered_image, active_image, alignment)
+        .interaction()
+}
+
+/// Same as `draw`, except that `disabled_image` is drawn if the context is disabled.
+pub fn draw_with_disabled<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState, normal_image: &I,
+                                hovered_image: &I, active_image: &I, disabled_image: &I,
+                                alignment: &Alignment) -> Interaction
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    draw_with_disabled_interactions(draw, ui_state, normal_image, hovered_image, active_image,
+                                    disabled_image, alignment).interaction()
 }
 
 /// Same as `image::stretch`, except that the image is clickable.
@@ -37,33 +49,99 @@ pub fn stretch<D: ?Sized + DrawImage<I>, I: ?Sized>(draw: &DrawContext<D>, ui_st
                                                     normal_image: &I, hovered_image: &I,
                                                     active_image: &I) -> Interaction
 {
-    let widget_id = draw.reserve_widget_id();
+    stretch_interactions(draw, ui_state, normal_image, hovered_image, active_image).interaction()
+}
+
+/// Same as `stretch`, except that `disabled_image` is drawn if the context is disabled.
+pub fn stretch_with_disabled<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState,
+                                   normal_image: &I, hovered_image: &I, active_image: &I,
+                                   disabled_image: &I) -> Interaction
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    stretch_with_disabled_interactions(draw, ui_state, normal_image, hovered_image, active_image,
+                                       disabled_image).interaction()
+}
+
+/// Same as `draw`, except that it returns the detailed `Interactions`.
+pub fn draw_interactions<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState, normal_image: &I,
+                               hovered_image: &I, active_image: &I, alignment: &Alignment)
+                               -> Interactions
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    draw_with_disabled_interactions(draw, ui_state, normal_image, hovered_image, active_image,
+                                    normal_image, alignment)
+}
+
+/// Same as `draw_with_disabled`, except that it returns the detailed `Interactions`.
+pub fn draw_with_disabled_interactions<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState,
+                                             normal_image: &I, hovered_image: &I,
+                                             active_image: &I, disabled_image: &I,
+                                             alignment: &Alignment) -> Interactions
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    let draw = draw.animation_stop();
+    let ratio = draw.draw().get_image_width_per_height(normal_image);
+    stretch_with_disabled_interactions(&draw.enforce_aspect_ratio_downscale(ratio, alignment),
+                                       ui_state, normal_image, hovered_image, active_image,
+                                       disabled_image)
+}
+
+/// Same as `stretch`, except that it returns the detailed `Interactions`.
+pub fn stretch_interactions<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState, normal_image: &I,
+                                  hovered_image: &I, active_image: &I) -> Interactions
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    stretch_with_disabled_interactions(draw, ui_state, normal_image, hovered_image, active_image,
+                                       normal_image)
+}
+
+/// Same as `stretch_with_disabled`, except that it returns the detailed `Interactions`.
+pub fn stretch_with_disabled_interactions<D, I>(draw: &DrawContext<D>, ui_state: &mut UiState,
+                                                normal_image: &I, hovered_image: &I,
+                                                active_image: &I, disabled_image: &I)
+                                                -> Interactions
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    let (interactions, look) = widgets::button(draw, ui_state);
+
+    let image = match look {
+        Look::Normal => normal_image,
+        Look::Hovered => hovered_image,
+        Look::Active => active_image,
+        Look::Disabled => disabled_image,
+    };
+    draw.draw().draw_image(image, &draw.matrix());
+
+    interactions
+}
 
-    if draw.is_cursor_hovering() {
-        draw.set_cursor_hovered_widget();
+#[cfg(test)]
+mod tests {
+    use UiState;
 
-        if Some(widget_id.clone()) == ui_state.active_widget {
-            draw.draw().draw_image(active_image, &draw.matrix());
+    use widgets::image_button;
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
 
-            if draw.cursor_was_released() {
-                ui_state.active_widget = None;
-                Interaction::Clicked
-            } else {
-                Interaction::None
-            }
+    #[test]
+    fn disabled() {
+        let mut ui_state = UiState::default();
+        let mut drawer = Recorder::default();
 
-        } else if draw.cursor_was_pressed() {
-            draw.draw().draw_image(active_image, &draw.matrix());
-            ui_state.active_widget = Some(widget_id.clone());
-            Interaction::None
+        {
+            let cursor = cursor_gl([100.0, 100.0], [50.0, 50.0]);
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some(cursor), true, false);
+            let ctxt = ctxt.disabled(true).disabled(false);
+            assert!(ctxt.is_disabled());
+            assert!(!ctxt.cursor_was_pressed());
 
-        } else {
-            draw.draw().draw_image(hovered_image, &draw.matrix());
-            Interaction::None
+            let interaction = image_button::stretch_with_disabled(&ctxt, &mut ui_state, "normal",
+                                                                  "hovered", "active",
+                                                                  "disabled");
+            assert!(!interaction.clicked());
         }
 
-    } else {
-        draw.draw().draw_image(normal_image, &draw.matrix());
-        Interaction::None
+        assert_eq!(ui_state.active_widget, None);
+        assert_eq!(drawer.images, vec!["disabled"]);
     }
 }
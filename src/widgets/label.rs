@@ -23,6 +23,7 @@ use matrix::Matrix;
 /// This is usually the function that you want in order to draw text. Even though the text
 /// can overflow its container if it is too long, it is usually visually better to have an
 /// overflow than to have multiple texts of different heights when they should be the same.
+#[allow(clippy::collapsible_if, clippy::needless_borrow)]
 pub fn flow<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_style: &T, text: &str,
                                                 alignment: &HorizontalAlignment)
 {
@@ -43,6 +44,7 @@ pub fn flow<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_styl
 
 /// Draws text. The text will be sized so that it is entirely contained within the context, and
 /// either its width or its height is equal to the width or the height of the context.
+#[allow(clippy::collapsible_if)]
 pub fn contain<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_style: &T, text: &str,
                                                    alignment: &Alignment)
 {
@@ -62,6 +64,7 @@ pub fn contain<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_s
 
 /// Draws text. The text will be sized so that it entirely covers the context, and either its
 /// width or its height is equal to the width or the height of the context.
+#[allow(clippy::collapsible_if)]
 pub fn cover<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_style: &T, text: &str,
                                                  alignment: &Alignment)
 {
@@ -79,9 +82,39 @@ pub fn cover<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_sty
     })
 }
 
+/// Returns the width of the text divided by its height, once drawn with any of the functions of
+/// this module.
+///
+/// This can be used to give a context the same aspect ratio as some text.
+pub fn width_per_height<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_style: &T,
+                                                            text: &str) -> f32
+{
+    glyphs(draw, text_style, text).1
+}
+
 fn helper<D: ?Sized + DrawText<T>, T: ?Sized, F>(draw: &DrawContext<D>, text_style: &T, text: &str,
                                                  final_matrix: F)
     where F: FnOnce(f32) -> Matrix
+{
+    let (glyphs, x) = glyphs(draw, text_style, text);
+
+    // So far the matrix of each character is in a coordinate system where 1.0 unit is equal to 1.0
+    // EM and the bottom-left corner of the first glyph is 0.0. Y=1.0 is the top of the line of
+    // text. We have to adjust this coordinates system for the final output.
+    let recenter_matrix = Matrix::scale_wh(2.0 / x, 2.0)
+            * Matrix::translate(-x / 2.0, -0.5);
+
+    let final_matrix = final_matrix(x);
+
+    for (chr, matrix) in glyphs.into_iter() {
+        draw.draw().draw_glyph(text_style, chr, &(final_matrix * recenter_matrix * matrix));
+    } 
+}
+
+/// Calculates the position of each glyph of the text, and the width of the text in ems.
+#[allow(clippy::mem_replace_option_with_some)]
+fn glyphs<D: ?Sized + DrawText<T>, T: ?Sized>(draw: &DrawContext<D>, text_style: &T, text: &str)
+                                              -> (Vec<(char, Matrix)>, f32)
 {
     let mut glyphs: Vec<(char, Matrix)> = Vec::with_capacity(text.len());
 
@@ -113,16 +146,5 @@ fn helper<D: ?Sized + DrawText<T>, T: ?Sized, F>(draw: &DrawContext<D>, text_sty
     }
 
     // `x` now contains the width of the text in ems.
-
-    // So far the matrix of each character is in a coordinate system where 1.0 unit is equal to 1.0
-    // EM and the bottom-left corner of the first glyph is 0.0. Y=1.0 is the top of the line of
-    // text. We have to adjust this coordinates system for the final output.
-    let recenter_matrix = Matrix::scale_wh(2.0 / x, 2.0)
-            * Matrix::translate(-x / 2.0, -0.5);
-
-    let final_matrix = final_matrix(x);
-
-    for (chr, matrix) in glyphs.into_iter() {
-        draw.draw().draw_glyph(text_style, chr, &(final_matrix * recenter_matrix * matrix));
-    } 
+    (glyphs, x)
 }
@@ -0,0 +1,286 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A list view shows a list of items, one per row, among which the user can select one or
+//! multiple items.
+//!
+//! The content of each row is drawn by a function provided by the caller. The list is
+//! virtualized: this function is only called for the rows that are visible, which makes it
+//! possible to show lists of thousands of items.
+//!
+//! The selection and the scrolling position are stored in a `State` owned by the caller.
+//!
+//! Once the list has been clicked, it takes the keyboard focus. The selection can then be moved
+//! with the arrow keys, the page up and page down keys, and the home and end keys. In multiple
+//! selection mode, these keys only move the current item, and the space key toggles whether the
+//! current item is selected.
+//!
+//! The list also scrolls by one row per line scrolled with the mouse wheel while the cursor is
+//! over it.
+
+use Alignment;
+use DrawImage;
+use DrawContext;
+use Key;
+use UiState;
+
+use widgets;
+
+/// State of a list view.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct State {
+    /// Indices of the items that are selected.
+    pub selection: Vec<usize>,
+
+    /// Index of the item that is moved with the keyboard.
+    pub current: Option<usize>,
+
+    /// Index of the first visible row.
+    pub scroll: usize,
+}
+
+impl State {
+    /// Returns true if the item at the given index is selected.
+    #[inline]
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selection.contains(&index)
+    }
+}
+
+/// Whether multiple items can be selected at the same time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Clicking an item selects it and deselects the others.
+    Single,
+    /// Clicking an item toggles whether it is selected.
+    Multiple,
+}
+
+/// Draws a list view over the whole area of the context.
+///
+/// `row_height` is the height of each row in logical pixels. The `hovered_image` is drawn behind
+/// the row that is hovered and the `selected_image` behind the rows that are selected. Then
+/// `draw_row` is called with the context of the row, the index of the item and the item, for
+/// each visible row.
+///
+/// Returns `true` if the selection has changed.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<'b, D, I, T, F>(draw: &DrawContext<'b, D>, ui_state: &mut UiState, state: &mut State,
+                            items: &[T], row_height: f32, selection_mode: SelectionMode,
+                            hovered_image: &I, selected_image: &I, mut draw_row: F) -> bool
+    where D: ?Sized + DrawImage<I>, I: ?Sized, F: FnMut(&DrawContext<'b, D>, usize, &T)
+{
+    let draw = draw.animation_stop();
+    let widget_id = draw.reserve_widget_id();
+    let previous_selection = state.selection.clone();
+
+    state.selection.retain(|&i| i < items.len());
+    if let Some(current) = state.current {
+        if current >= items.len() {
+            state.current = None;
+        }
+    }
+
+    let row_height_percent = row_height / draw.height_pixels();
+    let visible_rows = (1.0 / row_height_percent).floor().max(1.0) as usize;
+
+    // Keyboard navigation.
+    if ui_state.focused_widget == Some(widget_id.clone()) && !items.is_empty() {
+        let last = items.len() - 1;
+        let current = state.current;
+        let moved_to = if draw.key_was_pressed(Key::Up) {
+            Some(current.map_or(last, |c| c.saturating_sub(1)))
+        } else if draw.key_was_pressed(Key::Down) {
+            Some(current.map_or(0, |c| if c < last { c + 1 } else { last }))
+        } else if draw.key_was_pressed(Key::PageUp) {
+            Some(current.map_or(0, |c| c.saturating_sub(visible_rows)))
+        } else if draw.key_was_pressed(Key::PageDown) {
+            Some(current.map_or(0, |c| {
+                if c + visible_rows < last { c + visible_rows } else { last }
+            }))
+        } else if draw.key_was_pressed(Key::Home) {
+            Some(0)
+        } else if draw.key_was_pressed(Key::End) {
+            Some(last)
+        } else {
+            None
+        };
+
+        if let Some(moved_to) = moved_to {
+            state.current = Some(moved_to);
+            if selection_mode == SelectionMode::Single {
+                state.selection = vec![moved_to];
+            }
+
+            // Making sure that the current item is visible.
+            if moved_to < state.scroll {
+                state.scroll = moved_to;
+            } else if moved_to >= state.scroll + visible_rows {
+                state.scroll = moved_to + 1 - visible_rows;
+            }
+        }
+
+        if selection_mode == SelectionMode::Multiple && draw.key_was_pressed(Key::Space) {
+            if let Some(current) = state.current {
+                toggle(state, current);
+            }
+        }
+    }
+
+    widgets::scroll_with_wheel(&draw, ui_state, &mut state.scroll);
+
+    if state.scroll + visible_rows > items.len() {
+        state.scroll = items.len().saturating_sub(visible_rows);
+    }
+
+    // Handling the cursor.
+    let hovered = draw.hit_test(ui_state, &widget_id);
+    if hovered && draw.cursor_was_pressed() {
+        ui_state.active_widget = Some(widget_id.clone());
+        ui_state.focused_widget = Some(widget_id.clone());
+    }
+
+    let end = if state.scroll + visible_rows < items.len() { state.scroll + visible_rows }
+              else { items.len() };
+
+    for (row_num, index) in (state.scroll .. end).enumerate() {
+        let row = draw.absolute(0.0, row_num as f32 * row_height_percent, 1.0, row_height_percent,
+                                &Alignment::top_left());
+        let row_hovered = hovered && row.is_cursor_hovering();
+
+        if row_hovered && draw.cursor_was_released() &&
+           ui_state.active_widget == Some(widget_id.clone())
+        {
+            ui_state.active_widget = None;
+            state.current = Some(index);
+
+            match selection_mode {
+                SelectionMode::Single => state.selection = vec![index],
+                SelectionMode::Multiple => toggle(state, index),
+            }
+        }
+
+        if state.is_selected(index) {
+            row.draw().draw_image(selected_image, &row.matrix());
+        } else if row_hovered {
+            row.draw().draw_image(hovered_image, &row.matrix());
+        }
+
+        draw_row(&row, index, &items[index]);
+    }
+
+    state.selection != previous_selection
+}
+
+/// Selects the item if it's not selected, and deselects it otherwise.
+fn toggle(state: &mut State, index: usize) {
+    if let Some(pos) = state.selection.iter().position(|&i| i == index) {
+        state.selection.remove(pos);
+    } else {
+        state.selection.push(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ButtonState;
+    use Input;
+    use Key;
+    use UiState;
+
+    use widgets::list;
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+
+    /// Draws a frame of a list of 50 items in a viewport of 100x100 pixels, with rows of 10
+    /// pixels. `row` is the row hovered by the cursor, if any. Returns whether the selection
+    /// changed, and the items that have been drawn.
+    fn frame(ui_state: &mut UiState, state: &mut list::State, mode: list::SelectionMode,
+             row: Option<usize>, pressed: bool, released: bool, keys: &[Key])
+             -> (bool, Vec<usize>)
+    {
+        let mut drawer = Recorder::default();
+        let cursor = row.map(|r| cursor_gl([100.0, 100.0], [50.0, r as f32 * 10.0 + 5.0]));
+        let mut input = Input::new(cursor);
+        input.left = ButtonState { down: pressed, pressed, released };
+        input.keys = keys.to_vec();
+        let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+
+        let items: Vec<u32> = (0 .. 50).collect();
+        let mut drawn = Vec::new();
+        let changed = list::draw(&ctxt, ui_state, state, &items, 10.0, mode, "hovered",
+                                 "selected", |_, index, _| drawn.push(index));
+        (changed, drawn)
+    }
+
+    #[test]
+    fn keyboard_navigation() {
+        let mut ui_state = UiState::default();
+        let mut state = list::State::default();
+        let mode = list::SelectionMode::Single;
+
+        // Clicking the third row gives the focus to the list.
+        frame(&mut ui_state, &mut state, mode, Some(2), true, false, &[]);
+        let (changed, _) = frame(&mut ui_state, &mut state, mode, Some(2), false, true, &[]);
+        assert!(changed);
+        assert_eq!(state.selection, vec![2]);
+
+        let steps = [(Key::Down, 3, 0), (Key::PageDown, 13, 4), (Key::End, 49, 40),
+                     (Key::PageUp, 39, 39), (Key::Home, 0, 0), (Key::Up, 0, 0)];
+        for &(key, current, scroll) in steps.iter() {
+            let (_, drawn) = frame(&mut ui_state, &mut state, mode, None, false, false, &[key]);
+            assert_eq!(state.current, Some(current));
+            assert_eq!(state.selection, vec![current]);
+            assert_eq!(state.scroll, scroll);
+            assert_eq!(drawn, (scroll .. scroll + 10).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn multiple_selection() {
+        let mut ui_state = UiState::default();
+        let mut state = list::State::default();
+        let mode = list::SelectionMode::Multiple;
+
+        for &row in [1, 3, 1].iter() {
+            frame(&mut ui_state, &mut state, mode, Some(row), true, false, &[]);
+            let (changed, _) = frame(&mut ui_state, &mut state, mode, Some(row), false, true, &[]);
+            assert!(changed);
+        }
+        assert_eq!(state.selection, vec![3]);
+
+        // The arrow keys only move the current item, and space toggles its selection.
+        let (changed, _) = frame(&mut ui_state, &mut state, mode, None, false, false, &[Key::Down]);
+        assert!(!changed);
+        assert_eq!(state.current, Some(2));
+        let (changed, _) = frame(&mut ui_state, &mut state, mode, None, false, false,
+                                 &[Key::Space]);
+        assert!(changed);
+        assert_eq!(state.selection, vec![3, 2]);
+    }
+
+    #[test]
+    fn wheel() {
+        let mut ui_state = UiState::default();
+        let mut state = list::State::default();
+        let items: Vec<u32> = (0 .. 50).collect();
+
+        for &(lines, scroll) in [(3.0, 3), (-5.0, 0), (100.0, 40), (-2.0, 38)].iter() {
+            let mut drawer = Recorder::default();
+            let mut input = Input::new(Some(cursor_gl([100.0, 100.0], [50.0, 50.0])));
+            input.wheel = [0.0, lines];
+            let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+
+            let mut drawn = Vec::new();
+            list::draw(&ctxt, &mut ui_state, &mut state, &items, 10.0,
+                       list::SelectionMode::Single, "hovered", "selected",
+                       |_, index, _| drawn.push(index));
+            assert_eq!(state.scroll, scroll);
+            assert_eq!(drawn, (scroll .. scroll + 10).collect::<Vec<_>>());
+        }
+    }
+}
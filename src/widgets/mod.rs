@@ -10,12 +10,38 @@
 //! Each module corresponds to a widget. See the individual module documentations for more info.
 
 pub mod circular_progress_bar;
+pub mod drag_drop;
+pub mod dropdown;
 pub mod image;
 pub mod image9;
 pub mod image_button;
 pub mod image9_button;
 pub mod label;
+pub mod list;
+pub mod modal;
 pub mod progress_bar;
+pub mod radio;
+pub mod table;
+pub mod tabs;
+pub mod text_button;
+pub mod tooltip;
+pub mod tree;
+
+use std::time::Duration;
+
+use CursorIcon;
+use DrawContext;
+use MouseButton;
+use Navigation;
+use UiState;
+
+use layout::Press;
+
+/// Maximum duration between two clicks for them to be a double click, in milliseconds.
+const DOUBLE_CLICK_MS: u64 = 500;
+
+/// Duration after which a press is a long press, in milliseconds.
+const LONG_PRESS_MS: u64 = 500;
 
 /// Whether the cursor clicked on the widget.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -31,6 +57,7 @@ impl Interaction {
     /// Returns `true` if equal to `Clicked`. This function is useful so that you don't have to
     /// import the enum in scope.
     #[inline]
+    #[allow(clippy::match_like_matches_macro)]
     pub fn clicked(&self) -> bool {
         match self {
             &Interaction::Clicked => true,
@@ -38,3 +65,301 @@ impl Interaction {
         }
     }
 }
+
+/// Detailed description of what the user did with a widget during the current frame. See
+/// `interact`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[must_use]
+pub struct Interactions {
+    /// The cursor is over the widget, and no other widget covers it.
+    pub hovered: bool,
+    /// The widget has the focus. See `DrawContext::focusable`.
+    pub focused: bool,
+    /// The context is disabled. Apart from `hovered`, the widget doesn't receive any other
+    /// interaction. See `DrawContext::disabled`.
+    pub disabled: bool,
+    /// A mouse button went from up to down over the widget.
+    pub pressed: bool,
+    /// A mouse button that has been pressed over the widget in a previous frame is still down.
+    pub held: bool,
+    /// The left button has been pressed and released over the widget, or `Navigation::Accept`
+    /// has been pressed while the widget has the focus.
+    pub clicked: bool,
+    /// A mouse button has been pressed over the widget, but released somewhere else.
+    pub released_outside: bool,
+    /// The left button is held and the cursor moved by this number of logical pixels since the
+    /// previous frame.
+    pub drag_delta: Option<[f32; 2]>,
+    /// The widget has been clicked twice in a short time. `clicked` is also true.
+    pub double_clicked: bool,
+    /// A mouse button has been held over the widget for a while. Only reported once per press.
+    pub long_pressed: bool,
+    /// The right button has been pressed and released over the widget.
+    pub secondary_clicked: bool,
+    /// The middle button has been pressed and released over the widget.
+    pub middle_clicked: bool,
+}
+
+impl Interactions {
+    /// Returns the value of `clicked`. This lets code that used to call `Interaction::clicked`
+    /// keep working when switching to `Interactions`.
+    #[inline]
+    pub fn clicked(&self) -> bool {
+        self.clicked
+    }
+
+    /// Turns this into an `Interaction`, which only tells whether the widget has been clicked.
+    #[inline]
+    pub fn interaction(&self) -> Interaction {
+        if self.clicked { Interaction::Clicked } else { Interaction::None }
+    }
+}
+
+/// Handles the interactions of the user with a widget that covers the whole area of the context.
+///
+/// This reserves a widget ID and registers the area with `DrawContext::hit_test` and
+/// `DrawContext::focusable`. The state of a press is stored in the `UiState` from one frame to
+/// the next. While the left button is held, the widget is also the active widget of the
+/// `UiState`.
+pub fn interact<D: ?Sized>(draw: &DrawContext<D>, ui_state: &mut UiState) -> Interactions {
+    let widget_id = draw.reserve_widget_id();
+    let now = draw.frame_time();
+
+    let mut result = Interactions {
+        hovered: draw.hit_test(ui_state, &widget_id),
+        focused: draw.focusable(ui_state, &widget_id),
+        disabled: draw.is_disabled(),
+        .. Interactions::default()
+    };
+
+    if result.focused && draw.navigation_was_pressed(Navigation::Accept) {
+        result.clicked = true;
+    }
+
+    let press = match ui_state.press {
+        Some(ref press) if press.widget == widget_id => ui_state.press.take(),
+        _ => None,
+    };
+
+    if let Some(mut press) = press {
+        if draw.button_was_released(press.button) {
+            if press.button == MouseButton::Left &&
+               ui_state.active_widget == Some(widget_id.clone())
+            {
+                ui_state.active_widget = None;
+            }
+
+            if !result.hovered {
+                result.released_outside = true;
+                return result;
+            }
+
+            match press.button {
+                MouseButton::Left => {
+                    result.clicked = true;
+
+                    let double_click = Duration::from_millis(DOUBLE_CLICK_MS);
+                    result.double_clicked = match ui_state.last_click {
+                        Some((ref id, time)) if *id == widget_id => {
+                            now.duration_since(time).map(|d| d <= double_click).unwrap_or(false)
+                        },
+                        _ => false,
+                    };
+
+                    // A third click starts a new double click.
+                    ui_state.last_click = if result.double_clicked { None }
+                                          else { Some((widget_id.clone(), now)) };
+                },
+                MouseButton::Right => result.secondary_clicked = true,
+                MouseButton::Middle => result.middle_clicked = true,
+            }
+
+        } else {
+            result.held = true;
+
+            let position = draw.cursor_position_pixels();
+            if press.button == MouseButton::Left {
+                if let (Some(last), Some(position)) = (press.last_position, position) {
+                    if last != position {
+                        result.drag_delta = Some([position[0] - last[0], position[1] - last[1]]);
+                    }
+                }
+            }
+            if position.is_some() {
+                press.last_position = position;
+            }
+
+            let long_press = Duration::from_millis(LONG_PRESS_MS);
+            if !press.long_press_reported &&
+               now.duration_since(press.start).map(|d| d >= long_press).unwrap_or(false)
+            {
+                result.long_pressed = true;
+                press.long_press_reported = true;
+            }
+
+            ui_state.press = Some(press);
+        }
+
+    } else if result.hovered {
+        let buttons = [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+        if let Some(&button) = buttons.iter().find(|&&b| draw.button_was_pressed(b)) {
+            result.pressed = true;
+            ui_state.press = Some(Press {
+                widget: widget_id.clone(),
+                button,
+                start: now,
+                last_position: draw.cursor_position_pixels(),
+                long_press_reported: false,
+            });
+
+            if button == MouseButton::Left {
+                ui_state.active_widget = Some(widget_id.clone());
+            }
+        }
+    }
+
+    result
+}
+
+/// Which of its images a button must draw. See `button`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Look {
+    Normal,
+    Hovered,
+    Active,
+    Disabled,
+}
+
+/// Handles the interactions with a button with `interact`, requests the matching cursor icon,
+/// and returns which image the button must draw.
+fn button<D: ?Sized>(draw: &DrawContext<D>, ui_state: &mut UiState) -> (Interactions, Look) {
+    let interactions = interact(draw, ui_state);
+
+    let look = if interactions.disabled {
+        Look::Disabled
+    } else if interactions.clicked ||
+              (interactions.hovered && (interactions.pressed || interactions.held))
+    {
+        Look::Active
+    } else if interactions.hovered || interactions.focused {
+        Look::Hovered
+    } else {
+        Look::Normal
+    };
+
+    if interactions.hovered {
+        draw.request_cursor_icon(if interactions.disabled { CursorIcon::NotAllowed }
+                                 else { CursorIcon::Hand });
+    }
+
+    (interactions, look)
+}
+
+/// Moves `scroll`, the index of the first visible row of a widget, by one row per line scrolled
+/// with the mouse wheel while the cursor is over the context. The caller must then make sure
+/// that `scroll` doesn't go past the last rows.
+fn scroll_with_wheel<D: ?Sized>(draw: &DrawContext<D>, ui_state: &mut UiState, scroll: &mut usize) {
+    if !draw.is_cursor_hovering_uncovered(ui_state) {
+        return;
+    }
+
+    let lines = draw.wheel_delta()[1].round();
+    if lines >= 0.0 {
+        *scroll += lines as usize;
+    } else {
+        *scroll = scroll.saturating_sub(-lines as usize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use DrawImage;
+    use DrawText;
+    use GlyphInfos;
+    use Input;
+    use Matrix;
+    use UiState;
+
+    use widgets;
+
+    /// Drawing object that records what the widgets draw. All the glyphs are squares.
+    #[derive(Debug, Default)]
+    pub struct Recorder {
+        /// Names of the images that have been drawn, in order.
+        pub images: Vec<String>,
+        /// Characters that have been drawn, in order.
+        pub text: String,
+    }
+
+    impl DrawImage<str> for Recorder {
+        fn draw_triangle(&mut self, _: &str, _: &Matrix, _: [[f32; 2]; 3]) {}
+
+        fn draw_image_uv(&mut self, name: &str, _: &Matrix, _: [f32; 2], _: [f32; 2], _: [f32; 2],
+                         _: [f32; 2])
+        {
+            self.images.push(name.to_owned());
+        }
+
+        fn get_image_width_per_height(&mut self, _: &str) -> f32 { 1.0 }
+    }
+
+    impl DrawText<str> for Recorder {
+        fn draw_glyph(&mut self, _: &str, glyph: char, _: &Matrix) {
+            self.text.push(glyph);
+        }
+
+        fn line_height(&self, _: &str) -> f32 { 1.0 }
+
+        fn glyph_infos(&self, _: &str, _: char) -> GlyphInfos {
+            GlyphInfos { width: 1.0, height: 1.0, x_offset: 0.0, y_offset: 1.0, x_advance: 1.0 }
+        }
+
+        fn kerning(&self, _: &str, _: char, _: char) -> f32 { 0.0 }
+    }
+
+    /// Turns a position in pixels from the top-left hand corner of a viewport of the given
+    /// dimensions into OpenGL coordinates, to be used as the position of the cursor.
+    pub fn cursor_gl(viewport: [f32; 2], pixels: [f32; 2]) -> [f32; 2] {
+        [pixels[0] / (viewport[0] / 2.0) - 1.0, 1.0 - pixels[1] / (viewport[1] / 2.0)]
+    }
+
+    #[test]
+    fn interactions() {
+        let mut ui_state = UiState::default();
+        let mut drawer = ();
+
+        // Pressing, dragging and releasing the left button.
+        let frames = [([50.0, 50.0], true, false), ([75.0, 50.0], false, false),
+                      ([75.0, 50.0], false, true), ([75.0, 50.0], true, false),
+                      ([75.0, 50.0], false, true)];
+        let mut results = Vec::new();
+        for &(cursor, pressed, released) in frames.iter() {
+            let cursor = cursor_gl([100.0, 100.0], cursor);
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some(cursor), pressed, released);
+            results.push(widgets::interact(&ctxt, &mut ui_state));
+        }
+
+        assert!(results[0].pressed && !results[0].held);
+        assert_eq!(ui_state.active_widget, None);
+        assert!(results[1].held);
+        assert_eq!(results[1].drag_delta, Some([25.0, 0.0]));
+        assert!(results[2].clicked && !results[2].double_clicked);
+        assert!(results[4].clicked && results[4].double_clicked);
+
+        // Clicking with the right button.
+        let mut input = Input::new(Some(cursor_gl([100.0, 100.0], [50.0, 50.0])));
+        input.right.pressed = true;
+        input.right.down = true;
+        let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+        let result = widgets::interact(&ctxt, &mut ui_state);
+        assert!(result.pressed);
+        drop(ctxt);
+
+        input.right.pressed = false;
+        input.right.down = false;
+        input.right.released = true;
+        let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+        let result = widgets::interact(&ctxt, &mut ui_state);
+        assert!(result.secondary_clicked && !result.clicked);
+    }
+}
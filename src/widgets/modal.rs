@@ -0,0 +1,229 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A modal dialog is a dialog that prevents the user from interacting with the rest of the UI
+//! while it is open.
+//!
+//! Whether the dialog is open is stored in a variable owned by the caller. A modal dialog is
+//! used in two steps:
+//!
+//! - Draw the rest of the UI with the context returned by `background`. If the dialog is open,
+//!   this context doesn't receive any input.
+//! - Then call `draw`, which dims the whole viewport and returns the context of the dialog, in
+//!   which you can draw the content of the dialog.
+//!
+//! ```
+//! # use immi::widgets::modal;
+//! # fn draw_main_menu<D: ?Sized + immi::DrawImage<str>>(_: &immi::DrawContext<D>) {}
+//! fn draw_ui<D>(ctxt: &immi::DrawContext<D>, ui_state: &mut immi::UiState, dialog_open: &mut bool)
+//!     where D: ?Sized + immi::DrawImage<str>
+//! {
+//!     draw_main_menu(&modal::background(ctxt, *dialog_open));
+//!
+//!     if let Some(dialog) = modal::draw(ctxt, ui_state, dialog_open, 0.5, 0.5, "dim",
+//!                                       Some(immi::Key::Escape), true)
+//!     {
+//!         immi::widgets::image::stretch(&dialog, "dialog_background");
+//!     }
+//! }
+//! ```
+
+use std::mem;
+
+use Alignment;
+use DrawImage;
+use DrawContext;
+use Key;
+use Navigation;
+use UiState;
+
+use widgets::image;
+
+/// Whether the dialog was open during the previous frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct WasOpen(bool);
+
+/// Returns a context identical to `draw`, except that it doesn't receive any input if `open` is
+/// true.
+///
+/// Use this context to draw everything that is behind the dialog.
+#[inline]
+pub fn background<'b, D: ?Sized>(draw: &DrawContext<'b, D>, open: bool) -> DrawContext<'b, D> {
+    if open {
+        draw.block_input()
+    } else {
+        draw.clone()
+    }
+}
+
+/// If `open` is true, draws `dim_image` over the whole viewport and returns the context of the
+/// dialog. The dialog is centered in the viewport, and its dimensions are a percentage of the
+/// dimensions of the viewport.
+///
+/// This function must be called after the rest of the UI has been drawn, so that the dim image is
+/// drawn on top of it. The dialog belongs to the layer above the one of `draw`, and covers the
+/// whole viewport for `DrawContext::hit_test`.
+///
+/// The dialog closes and `open` is set to false if `close_key` is pressed, or if
+/// `close_on_click_outside` is true and the user clicks outside of the dialog. If `close_key` is
+/// `Some`, `Navigation::Back` also closes the dialog. Any open popup is closed when the dialog
+/// opens.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<'b, D, I>(draw: &DrawContext<'b, D>, ui_state: &mut UiState, open: &mut bool,
+                      width_percent: f32, height_percent: f32, dim_image: &I,
+                      close_key: Option<Key>, close_on_click_outside: bool)
+                      -> Option<DrawContext<'b, D>>
+    where D: ?Sized + DrawImage<I>, I: ?Sized
+{
+    let widget_id = draw.reserve_widget_id();
+
+    let viewport = draw.viewport().input_overlay();
+    let dialog = viewport.rescale(width_percent, height_percent, &Alignment::center());
+
+    if *open {
+        let close_key_pressed = match close_key {
+            Some(key) => draw.key_was_pressed(key) ||
+                         draw.navigation_was_pressed(Navigation::Back),
+            None => false,
+        };
+
+        let clicked_outside = close_on_click_outside && viewport.cursor_was_pressed() &&
+                              !dialog.is_cursor_hovering();
+
+        if close_key_pressed || clicked_outside {
+            *open = false;
+        }
+    }
+
+    let was_open = {
+        let was_open = draw.widget_data(ui_state, &widget_id, || WasOpen(false));
+        mem::replace(&mut was_open.0, *open)
+    };
+
+    if !*open {
+        return None;
+    }
+
+    // The popups of the background could otherwise stay open on top of the dim image. The
+    // popups of the dialog itself are opened later, so this is only done when the dialog opens.
+    if !was_open {
+        ui_state.open_popup = None;
+    }
+
+    viewport.hit_test(ui_state, &widget_id);
+    image::stretch(&viewport, dim_image);
+
+    Some(dialog)
+}
+
+#[cfg(test)]
+mod tests {
+    use ButtonState;
+    use Input;
+    use Key;
+    use UiState;
+    use VerticalAlignment;
+
+    use widgets::dropdown;
+    use widgets::modal;
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+
+    /// Draws a frame with a dialog covering the middle half of a viewport of 100x100 pixels.
+    /// `cursor` is in pixels from the top-left hand corner. Returns whether the dialog is shown,
+    /// whether the background received the press of the button, and the images that have been
+    /// drawn.
+    fn frame(ui_state: &mut UiState, open: &mut bool, cursor: [f32; 2], pressed: bool,
+             keys: &[Key]) -> (bool, bool, Vec<String>)
+    {
+        let mut drawer = Recorder::default();
+
+        let (shown, background_pressed) = {
+            let mut input = Input::new(Some(cursor_gl([100.0, 100.0], cursor)));
+            input.left.pressed = pressed;
+            input.left.down = pressed;
+            input.keys = keys.to_vec();
+            let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+
+            let background_pressed = modal::background(&ctxt, *open).cursor_was_pressed();
+            let dialog = modal::draw(&ctxt, ui_state, open, 0.5, 0.5, "dim", Some(Key::Escape),
+                                     true);
+            (dialog.is_some(), background_pressed)
+        };
+
+        (shown, background_pressed, drawer.images)
+    }
+
+    #[test]
+    fn close_key() {
+        let mut ui_state = UiState::default();
+        let mut open = true;
+
+        let (shown, background_pressed, images) = frame(&mut ui_state, &mut open, [50.0, 50.0],
+                                                        true, &[]);
+        assert!(shown && !background_pressed);
+        assert_eq!(images, vec!["dim"]);
+
+        let (shown, _, images) = frame(&mut ui_state, &mut open, [50.0, 50.0], false,
+                                       &[Key::Escape]);
+        assert!(!shown && !open);
+        assert!(images.is_empty());
+
+        // Once closed, the background receives the input again.
+        let (_, background_pressed, _) = frame(&mut ui_state, &mut open, [50.0, 50.0], true,
+                                               &[]);
+        assert!(background_pressed);
+    }
+
+    #[test]
+    fn click_outside() {
+        let mut ui_state = UiState::default();
+        let mut open = true;
+
+        // Clicking inside the dialog keeps it open.
+        assert!(frame(&mut ui_state, &mut open, [60.0, 40.0], true, &[]).0);
+        assert!(open);
+
+        let (shown, _, _) = frame(&mut ui_state, &mut open, [90.0, 10.0], true, &[]);
+        assert!(!shown && !open);
+    }
+
+    #[test]
+    fn dropdown_inside() {
+        let mut ui_state = UiState::default();
+        let mut open = true;
+        let mut selection = 0;
+
+        // Draws a dropdown at the top of the dialog, with the cursor over its button. Returns
+        // whether the list of the dropdown has been drawn.
+        let mut frame = |ui_state: &mut UiState, pressed: bool, released: bool| {
+            let mut drawer = Recorder::default();
+
+            {
+                let mut input = Input::new(Some(cursor_gl([100.0, 100.0], [50.0, 30.0])));
+                input.left = ButtonState { down: pressed, pressed, released };
+                let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+
+                let dialog = modal::draw(&ctxt, ui_state, &mut open, 0.5, 0.5, "dim",
+                                         Some(Key::Escape), true).unwrap();
+                let button = dialog.vertical_rescale(0.2, &VerticalAlignment::Top);
+                dropdown::draw(&button, ui_state, &mut selection, &["a", "b", "c"], "normal",
+                               "hovered", "active", "list", "highlight", "text");
+            }
+
+            drawer.images.iter().any(|i| i == "list")
+        };
+
+        assert!(!frame(&mut ui_state, true, false));
+        assert!(frame(&mut ui_state, false, true));
+
+        // The list stays open in the next frames.
+        assert!(frame(&mut ui_state, false, false));
+        assert!(frame(&mut ui_state, false, false));
+        assert!(ui_state.open_popup.is_some());
+    }
+}
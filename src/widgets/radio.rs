@@ -0,0 +1,169 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Radio buttons are a group of image buttons among which only one can be selected.
+//!
+//! The selection is stored in a variable owned by the caller. It can be an index, a value of an
+//! enum, or anything that can be compared.
+//!
+//! Each option has four images: one for when the option is not selected, one for when it is
+//! hovered, one for when it is active, and one for when it is selected.
+
+use Alignment;
+use DrawImage;
+use DrawContext;
+use UiState;
+
+use widgets::Interaction;
+use widgets::image_button;
+
+/// Draws a single option of a group of radio buttons and keeps the aspect ratio of the normal
+/// image.
+///
+/// If the option is clicked, `selection` is set to `value`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<D, I, T>(draw: &DrawContext<D>, ui_state: &mut UiState, selection: &mut T, value: T,
+                     normal_image: &I, hovered_image: &I, active_image: &I, selected_image: &I,
+                     alignment: &Alignment) -> Interaction
+    where D: ?Sized + DrawImage<I>, I: ?Sized, T: PartialEq
+{
+    let draw = draw.animation_stop();
+    let ratio = draw.draw().get_image_width_per_height(normal_image);
+    stretch(&draw.enforce_aspect_ratio_downscale(ratio, alignment), ui_state, selection, value,
+            normal_image, hovered_image, active_image, selected_image)
+}
+
+/// Draws a single option of a group of radio buttons, stretching it over the whole area.
+///
+/// If the option is clicked, `selection` is set to `value`.
+#[allow(clippy::too_many_arguments)]
+pub fn stretch<D, I, T>(draw: &DrawContext<D>, ui_state: &mut UiState, selection: &mut T,
+                        value: T, normal_image: &I, hovered_image: &I, active_image: &I,
+                        selected_image: &I) -> Interaction
+    where D: ?Sized + DrawImage<I>, I: ?Sized, T: PartialEq
+{
+    let (normal_image, hovered_image) = if *selection == value {
+        (selected_image, selected_image)
+    } else {
+        (normal_image, hovered_image)
+    };
+
+    let interaction = image_button::stretch(draw, ui_state, normal_image, hovered_image,
+                                            active_image);
+
+    if interaction.clicked() {
+        *selection = value;
+    }
+
+    interaction
+}
+
+/// Draws a group of radio buttons, one for each value of `values`, by splitting the context
+/// vertically.
+///
+/// Returns `true` if the selection has changed.
+///
+/// # Panic
+///
+/// Panics if `values` is empty.
+#[allow(clippy::too_many_arguments)]
+pub fn vertical<D, I, T>(draw: &DrawContext<D>, ui_state: &mut UiState, selection: &mut T,
+                         values: &[T], normal_image: &I, hovered_image: &I, active_image: &I,
+                         selected_image: &I, alignment: &Alignment) -> bool
+    where D: ?Sized + DrawImage<I>, I: ?Sized, T: PartialEq + Clone
+{
+    let mut changed = false;
+
+    for (ctxt, value) in draw.vertical_split(values.len()).zip(values.iter()) {
+        let already_selected = *selection == *value;
+        let interaction = self::draw(&ctxt, ui_state, selection, value.clone(), normal_image,
+                                     hovered_image, active_image, selected_image, alignment);
+        if interaction.clicked() && !already_selected {
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Draws a group of radio buttons, one for each value of `values`, by splitting the context
+/// horizontally.
+///
+/// Returns `true` if the selection has changed.
+///
+/// # Panic
+///
+/// Panics if `values` is empty.
+#[allow(clippy::too_many_arguments)]
+pub fn horizontal<D, I, T>(draw: &DrawContext<D>, ui_state: &mut UiState, selection: &mut T,
+                           values: &[T], normal_image: &I, hovered_image: &I, active_image: &I,
+                           selected_image: &I, alignment: &Alignment) -> bool
+    where D: ?Sized + DrawImage<I>, I: ?Sized, T: PartialEq + Clone
+{
+    let mut changed = false;
+
+    for (ctxt, value) in draw.horizontal_split(values.len()).zip(values.iter()) {
+        let already_selected = *selection == *value;
+        let interaction = self::draw(&ctxt, ui_state, selection, value.clone(), normal_image,
+                                     hovered_image, active_image, selected_image, alignment);
+        if interaction.clicked() && !already_selected {
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use Alignment;
+    use UiState;
+
+    use widgets::radio;
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+
+    /// Draws a frame with a horizontal group of three radio buttons in a viewport of 300x100
+    /// pixels, with the cursor over the button at `index`. Returns whether the selection changed,
+    /// and the images that have been drawn.
+    fn frame(ui_state: &mut UiState, selection: &mut u32, index: usize, pressed: bool,
+             released: bool) -> (bool, Vec<String>)
+    {
+        let mut drawer = Recorder::default();
+
+        let changed = {
+            let cursor = cursor_gl([300.0, 100.0], [index as f32 * 100.0 + 50.0, 50.0]);
+            let ctxt = ::draw().draw(300.0, 100.0, &mut drawer, Some(cursor), pressed, released);
+            radio::horizontal(&ctxt, ui_state, selection, &[10, 20, 30], "normal", "hovered",
+                              "active", "selected", &Alignment::center())
+        };
+
+        (changed, drawer.images)
+    }
+
+    #[test]
+    fn change_selection() {
+        let mut ui_state = UiState::default();
+        let mut selection = 10;
+
+        let (changed, images) = frame(&mut ui_state, &mut selection, 1, false, false);
+        assert!(!changed);
+        assert_eq!(images, vec!["selected", "hovered", "normal"]);
+
+        frame(&mut ui_state, &mut selection, 1, true, false);
+        let (changed, _) = frame(&mut ui_state, &mut selection, 1, false, true);
+        assert!(changed);
+        assert_eq!(selection, 20);
+
+        // Clicking the option that is already selected doesn't report a change.
+        frame(&mut ui_state, &mut selection, 1, true, false);
+        let (changed, images) = frame(&mut ui_state, &mut selection, 1, false, true);
+        assert!(!changed);
+        assert_eq!(selection, 20);
+        assert_eq!(images, vec!["normal", "active", "normal"]);
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A table shows data organized in rows and columns, below a header row with the title of each
+//! column.
+//!
+//! The width of each column is a weight, like with `DrawContext::horizontal_split_weights`. The
+//! weights are stored in a `State` owned by the caller, and the user can change them by dragging
+//! the border between two headers.
+//!
+//! Clicking a header sorts the table by this column, and clicking it again reverses the order.
+//! The table doesn't sort the data by itself: it only stores the sort column and order in the
+//! `State` and draws an indicator in the header. The caller must sort the rows accordingly.
+//!
+//! Like lists, tables are virtualized: the content of a row is only drawn if the row is visible.
+//! They also scroll by one row per line scrolled with the mouse wheel while the cursor is over
+//! them.
+
+use Alignment;
+use CursorIcon;
+use DrawImage;
+use DrawText;
+use DrawContext;
+use HorizontalAlignment;
+use UiState;
+
+use widgets;
+use widgets::label;
+
+/// Distance from the border between two headers, in logical pixels, under which pressing the
+/// cursor starts resizing the columns.
+const RESIZE_HANDLE_WIDTH: f32 = 4.0;
+
+/// Minimum width of a column when resizing, in logical pixels.
+const MIN_COLUMN_WIDTH: f32 = 16.0;
+
+/// State of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct State {
+    /// Width of each column, in an arbitrary unit.
+    pub column_weights: Vec<f32>,
+
+    /// Index of the column the table is sorted by, and the order.
+    pub sort: Option<(usize, SortOrder)>,
+
+    /// Index of the first visible row.
+    pub scroll: usize,
+
+    /// Index of the column whose right border is being dragged.
+    pub resizing: Option<usize>,
+}
+
+impl State {
+    /// Builds a `State` for a table with the given number of columns of the same width.
+    #[inline]
+    pub fn new(columns: usize) -> State {
+        State {
+            column_weights: vec![1.0; columns],
+            sort: None,
+            scroll: 0,
+            resizing: None,
+        }
+    }
+}
+
+/// Order in which the rows of a table are sorted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    /// From the smallest to the largest value.
+    Ascending,
+    /// From the largest to the smallest value.
+    Descending,
+}
+
+/// Draws a table over the whole area of the context.
+///
+/// `header_height` is the height of the header row and `row_height` the height of each row of
+/// the body, both in logical pixels. The headers are drawn with `header_image`, or
+/// `hovered_image` when hovered, and their title is drawn over them. The `ascending_image` or
+/// `descending_image` is drawn in a square at the right of the header of the column the table is
+/// sorted by.
+///
+/// Then `draw_cell` is called with the context of the cell, the index of the row and the index of
+/// the column, for each cell of the visible rows.
+///
+/// Returns `true` if the sort column or order has changed.
+///
+/// # Panic
+///
+/// Panics if `titles` is empty or if its length is different from the number of weights in the
+/// state.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<'b, D, I, T, S, F>(draw: &DrawContext<'b, D>, ui_state: &mut UiState,
+                               state: &mut State, titles: &[S], row_count: usize,
+                               header_height: f32, row_height: f32, header_image: &I,
+                               hovered_image: &I, ascending_image: &I, descending_image: &I,
+                               text_style: &T, mut draw_cell: F) -> bool
+    where D: ?Sized + DrawImage<I> + DrawText<T>, I: ?Sized, T: ?Sized, S: AsRef<str>,
+          F: FnMut(&DrawContext<'b, D>, usize, usize)
+{
+    assert!(!titles.is_empty());
+    assert_eq!(titles.len(), state.column_weights.len());
+
+    let draw = draw.animation_stop();
+    let width = draw.width_pixels();
+    let header = draw.absolute_pixels(0.0, 0.0, width, header_height, &Alignment::top_left());
+    let body = draw.absolute_pixels(0.0, header_height, width,
+                                    (draw.height_pixels() - header_height).max(0.0),
+                                    &Alignment::top_left());
+
+    let previous_sort = state.sort;
+    let widget_id = draw.reserve_widget_id();
+    let header_hovered = header.hit_test(ui_state, &widget_id);
+
+    // Position of the cursor relative to the left border of the header, in logical pixels.
+    let header_rect = header.rect_pixels();
+    let cursor_x = draw.cursor_position_pixels().map(|c| c[0] - header_rect.x);
+    let total_weight: f32 = state.column_weights.iter().sum();
+    let pixels_per_weight = header_rect.width / total_weight;
+
+    // Column under the cursor, and column whose right border is under the cursor.
+    let mut hovered_column = None;
+    let mut hovered_border = None;
+    if let Some(cursor_x) = cursor_x {
+        let mut left = 0.0;
+        for (num, weight) in state.column_weights.iter().enumerate() {
+            let right = left + weight * pixels_per_weight;
+            if cursor_x >= left && cursor_x < right {
+                hovered_column = Some(num);
+            }
+            if num + 1 < state.column_weights.len() &&
+               (cursor_x - right).abs() <= RESIZE_HANDLE_WIDTH
+            {
+                hovered_border = Some(num);
+            }
+            left = right;
+        }
+    }
+
+    if header_hovered && draw.cursor_was_pressed() {
+        ui_state.active_widget = Some(widget_id.clone());
+        state.resizing = hovered_border;
+    }
+
+    if state.resizing.is_some() || (header_hovered && hovered_border.is_some()) {
+        draw.request_cursor_icon(CursorIcon::ResizeHorizontal);
+    }
+
+    if ui_state.active_widget == Some(widget_id.clone()) {
+        if let (Some(column), Some(cursor_x)) = (state.resizing, cursor_x) {
+            let left = state.column_weights[.. column].iter().sum::<f32>() * pixels_per_weight;
+            let pair = (state.column_weights[column] + state.column_weights[column + 1]) *
+                       pixels_per_weight;
+
+            let mut new_width = cursor_x - left;
+            if new_width > pair - MIN_COLUMN_WIDTH { new_width = pair - MIN_COLUMN_WIDTH; }
+            if new_width < MIN_COLUMN_WIDTH { new_width = MIN_COLUMN_WIDTH; }
+
+            if pair >= 2.0 * MIN_COLUMN_WIDTH {
+                state.column_weights[column] = new_width / pixels_per_weight;
+                state.column_weights[column + 1] = (pair - new_width) / pixels_per_weight;
+            }
+        }
+
+        if draw.cursor_was_released() {
+            ui_state.active_widget = None;
+
+            if let (None, true, Some(column)) = (state.resizing, header_hovered, hovered_column) {
+                state.sort = match state.sort {
+                    Some((c, SortOrder::Ascending)) if c == column => {
+                        Some((column, SortOrder::Descending))
+                    },
+                    _ => Some((column, SortOrder::Ascending)),
+                };
+            }
+
+            state.resizing = None;
+        }
+
+    } else {
+        state.resizing = None;
+    }
+
+    // Drawing the headers.
+    let weights = state.column_weights.clone();
+    for (num, (cell, title)) in header.horizontal_split_weights(weights.iter().cloned())
+                                      .zip(titles.iter()).enumerate()
+    {
+        let image = if header_hovered && hovered_column == Some(num) && state.resizing.is_none() {
+            hovered_image
+        } else {
+            header_image
+        };
+        cell.draw().draw_image(image, &cell.matrix());
+
+        let indicator = match state.sort {
+            Some((c, SortOrder::Ascending)) if c == num => Some(ascending_image),
+            Some((c, SortOrder::Descending)) if c == num => Some(descending_image),
+            _ => None,
+        };
+
+        if let Some(indicator) = indicator {
+            let area = cell.horizontal_rescale_pixels(header_height, &HorizontalAlignment::Right);
+            area.draw().draw_image(indicator, &area.matrix());
+        }
+
+        let padding = header_height * 0.25;
+        label::contain(&cell.margin_pixels(padding, header_height, padding, padding), text_style,
+                       title.as_ref(), &Alignment::left());
+    }
+
+    // Drawing the body.
+    let visible_rows = (body.height_pixels() / row_height).floor().max(1.0) as usize;
+    widgets::scroll_with_wheel(&draw, ui_state, &mut state.scroll);
+    if state.scroll + visible_rows > row_count {
+        state.scroll = row_count.saturating_sub(visible_rows);
+    }
+
+    let end = if state.scroll + visible_rows < row_count { state.scroll + visible_rows }
+              else { row_count };
+
+    for (row_num, row_index) in (state.scroll .. end).enumerate() {
+        let row = body.absolute_pixels(0.0, row_num as f32 * row_height, width, row_height,
+                                       &Alignment::top_left());
+
+        for (column, cell) in row.horizontal_split_weights(weights.iter().cloned()).enumerate() {
+            draw_cell(&cell, row_index, column);
+        }
+    }
+
+    state.sort != previous_sort
+}
+
+#[cfg(test)]
+mod tests {
+    use Input;
+    use UiState;
+
+    use widgets::table;
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+
+    /// Draws a frame of a table of 100 rows in a viewport of 200x100 pixels, with a header of
+    /// 20 pixels and rows of 10 pixels. Returns whether the sort changed, and the cells that have
+    /// been drawn.
+    fn frame(ui_state: &mut UiState, state: &mut table::State, cursor: Option<[f32; 2]>,
+             pressed: bool, released: bool) -> (bool, Vec<(usize, usize)>)
+    {
+        let mut drawer = Recorder::default();
+        let cursor = cursor.map(|c| cursor_gl([200.0, 100.0], c));
+        let ctxt = ::draw().draw(200.0, 100.0, &mut drawer, cursor, pressed, released);
+
+        let mut cells = Vec::new();
+        let changed = table::draw(&ctxt, ui_state, state, &["a", "b"], 100, 20.0, 10.0, "header",
+                                  "hovered", "ascending", "descending", "text",
+                                  |_, row, column| cells.push((row, column)));
+        (changed, cells)
+    }
+
+    #[test]
+    fn sort() {
+        let mut ui_state = UiState::default();
+        let mut state = table::State::new(2);
+
+        let mut click = |state: &mut table::State, x| {
+            frame(&mut ui_state, state, Some([x, 10.0]), true, false);
+            frame(&mut ui_state, state, Some([x, 10.0]), false, true).0
+        };
+
+        assert!(click(&mut state, 50.0));
+        assert_eq!(state.sort, Some((0, table::SortOrder::Ascending)));
+        assert!(click(&mut state, 50.0));
+        assert_eq!(state.sort, Some((0, table::SortOrder::Descending)));
+        assert!(click(&mut state, 150.0));
+        assert_eq!(state.sort, Some((1, table::SortOrder::Ascending)));
+    }
+
+    #[test]
+    fn resize_columns() {
+        let mut ui_state = UiState::default();
+        let mut state = table::State::new(2);
+
+        // Pressing on the border between the two columns.
+        frame(&mut ui_state, &mut state, Some([101.0, 10.0]), true, false);
+        assert_eq!(state.resizing, Some(0));
+
+        frame(&mut ui_state, &mut state, Some([150.0, 10.0]), false, false);
+        assert!((state.column_weights[0] - 1.5).abs() < 0.001);
+        assert!((state.column_weights[1] - 0.5).abs() < 0.001);
+
+        // The second column can't become narrower than the minimum width.
+        frame(&mut ui_state, &mut state, Some([199.0, 10.0]), false, false);
+        assert!((state.column_weights[0] - 1.84).abs() < 0.001);
+        assert!((state.column_weights[1] - 0.16).abs() < 0.001);
+
+        // Releasing ends the resize without sorting.
+        let (changed, _) = frame(&mut ui_state, &mut state, Some([199.0, 10.0]), false, true);
+        assert!(!changed);
+        assert_eq!(state.resizing, None);
+        assert_eq!(state.sort, None);
+    }
+
+    #[test]
+    fn visible_rows() {
+        let mut ui_state = UiState::default();
+        let mut state = table::State::new(2);
+
+        // The body is 80 pixels high, which fits 8 rows.
+        let (_, cells) = frame(&mut ui_state, &mut state, None, false, false);
+        let expected: Vec<_> = (0 .. 8).flat_map(|r| vec![(r, 0), (r, 1)]).collect();
+        assert_eq!(cells, expected);
+
+        // Scrolling past the end shows the last rows.
+        state.scroll = 95;
+        let (_, cells) = frame(&mut ui_state, &mut state, None, false, false);
+        let expected: Vec<_> = (92 .. 100).flat_map(|r| vec![(r, 0), (r, 1)]).collect();
+        assert_eq!(cells, expected);
+        assert_eq!(state.scroll, 92);
+    }
+
+    #[test]
+    fn wheel() {
+        let mut ui_state = UiState::default();
+        let mut state = table::State::new(2);
+
+        for &(lines, scroll) in [(5.0, 5), (200.0, 92), (-10.0, 82)].iter() {
+            let mut drawer = Recorder::default();
+            let mut input = Input::new(Some(cursor_gl([200.0, 100.0], [100.0, 50.0])));
+            input.wheel = [0.0, lines];
+            let ctxt = ::draw().draw_with_input(200.0, 100.0, &mut drawer, &input);
+
+            let mut rows = Vec::new();
+            table::draw(&ctxt, &mut ui_state, &mut state, &["a", "b"], 100, 20.0, 10.0, "header",
+                        "hovered", "ascending", "descending", "text",
+                        |_, row, _| if !rows.contains(&row) { rows.push(row) });
+            assert_eq!(state.scroll, scroll);
+            assert_eq!(rows, (scroll .. scroll + 8).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A tab bar is a row of headers, one for each tab, above the content of the selected tab.
+//!
+//! The index of the selected tab is stored in a `State` owned by the caller. The headers behave
+//! like radio buttons: they use a normal, hovered, active and selected image, and the title of
+//! each tab is drawn over its header.
+//!
+//! Optionally, an indicator can be drawn at the bottom of the header of the selected tab. When
+//! the selection changes, the indicator slides from the previous tab to the new one.
+
+use std::time::Duration;
+use std::time::SystemTime;
+
+use Alignment;
+use DrawImage;
+use DrawText;
+use DrawContext;
+use UiState;
+use VerticalAlignment;
+
+use animations::EaseOut;
+use animations::Interpolation;
+use widgets::label;
+use widgets::radio;
+
+/// Duration of the animation of the indicator, in milliseconds.
+const INDICATOR_ANIMATION_MS: u64 = 250;
+
+/// Height of the indicator, as a percentage of the height of the headers.
+const INDICATOR_HEIGHT: f32 = 0.1;
+
+/// State of a tab bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    /// Index of the tab that is currently selected.
+    pub active: usize,
+
+    /// Index of the tab that was selected before the current one, and the moment when the
+    /// selection changed. Used to animate the indicator.
+    pub previous: Option<(usize, SystemTime)>,
+}
+
+impl State {
+    /// Builds a `State` where the tab at the given index is selected.
+    #[inline]
+    pub fn new(active: usize) -> State {
+        State {
+            active,
+            previous: None,
+        }
+    }
+}
+
+impl Default for State {
+    #[inline]
+    fn default() -> State {
+        State::new(0)
+    }
+}
+
+/// How the width of the headers is determined.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HeaderWidth {
+    /// All the headers have the same width.
+    Even,
+    /// The width of each header depends on the width of its title.
+    Content,
+}
+
+/// Draws a tab bar at the top of the context and returns the context where the content of the
+/// selected tab must be drawn.
+///
+/// `header_height` is the height of the headers, as a percentage of the height of the context.
+/// If `indicator_image` is `Some`, it is drawn at the bottom of the header of the selected tab.
+///
+/// # Panic
+///
+/// Panics if `titles` is empty.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<'b, D, I, T, S>(draw: &DrawContext<'b, D>, ui_state: &mut UiState, state: &mut State,
+                            titles: &[S], header_height: f32, header_width: HeaderWidth,
+                            normal_image: &I, hovered_image: &I, active_image: &I,
+                            selected_image: &I, indicator_image: Option<&I>, text_style: &T)
+                            -> DrawContext<'b, D>
+    where D: ?Sized + DrawImage<I> + DrawText<T>, I: ?Sized, T: ?Sized, S: AsRef<str>
+{
+    assert!(!titles.is_empty());
+
+    let draw = draw.animation_stop();
+    let headers = draw.vertical_rescale(header_height, &VerticalAlignment::Top);
+
+    // Width of each header, in an arbitrary unit.
+    let weights: Vec<f32> = match header_width {
+        HeaderWidth::Even => titles.iter().map(|_| 1.0).collect(),
+        HeaderWidth::Content => titles.iter().map(|title| {
+            // One em of padding on each side of the title.
+            label::width_per_height(&headers, text_style, title.as_ref()) + 2.0
+        }).collect(),
+    };
+
+    let previous_active = state.active;
+
+    for (num, (header, title)) in headers.horizontal_split_weights(weights.iter().cloned())
+                                         .zip(titles.iter()).enumerate()
+    {
+        let _ = radio::stretch(&header, ui_state, &mut state.active, num, normal_image,
+                               hovered_image, active_image, selected_image);
+        label::contain(&header.uniform_margin(0.15, 0.15, 0.15, 0.15), text_style,
+                       title.as_ref(), &Alignment::center());
+    }
+
+    if state.active != previous_active {
+        state.previous = Some((previous_active, draw.frame_time()));
+    }
+
+    if let Some(indicator_image) = indicator_image {
+        let total: f32 = weights.iter().sum();
+        let position = |index: usize| {
+            let index = if index < weights.len() { index } else { weights.len() - 1 };
+            let left = weights[.. index].iter().sum::<f32>() / total;
+            (left, weights[index] / total)
+        };
+
+        let (mut left, mut width) = position(state.active);
+
+        if let Some((previous, switch_time)) = state.previous {
+            let duration = Duration::from_millis(INDICATOR_ANIMATION_MS);
+            let progress = EaseOut::default().calculate(draw.frame_time(), switch_time,
+                                                        duration) as f32;
+            let progress = if progress > 1.0 { 1.0 } else { progress };
+            let (prev_left, prev_width) = position(previous);
+            left = prev_left + (left - prev_left) * progress;
+            width = prev_width + (width - prev_width) * progress;
+        }
+
+        let indicator = headers.absolute(left, 1.0, width, INDICATOR_HEIGHT,
+                                         &Alignment::bottom_left());
+        indicator.draw().draw_image(indicator_image, &indicator.matrix());
+    }
+
+    draw.vertical_rescale(1.0 - header_height, &VerticalAlignment::Bottom)
+}
+
+#[cfg(test)]
+mod tests {
+    use UiState;
+
+    use widgets::tabs;
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+
+    /// Draws a frame with three tabs in a viewport of 300x100 pixels, with headers of 20 pixels.
+    /// `cursor` is in pixels from the top-left hand corner. Returns the area of the content, in
+    /// the order x, y, width, height, and the images that have been drawn.
+    fn frame(ui_state: &mut UiState, state: &mut tabs::State, cursor: [f32; 2], pressed: bool,
+             released: bool) -> ([f32; 4], Vec<String>)
+    {
+        let mut drawer = Recorder::default();
+
+        let content = {
+            let cursor = cursor_gl([300.0, 100.0], cursor);
+            let ctxt = ::draw().draw(300.0, 100.0, &mut drawer, Some(cursor), pressed, released);
+            let content = tabs::draw(&ctxt, ui_state, state, &["a", "b", "c"], 0.2,
+                                     tabs::HeaderWidth::Even, "normal", "hovered", "active",
+                                     "selected", Some("indicator"), "text");
+            let rect = content.rect_pixels();
+            [rect.x, rect.y, rect.width, rect.height]
+        };
+
+        (content, drawer.images)
+    }
+
+    #[test]
+    fn select_tab() {
+        let mut ui_state = UiState::default();
+        let mut state = tabs::State::default();
+
+        let (content, images) = frame(&mut ui_state, &mut state, [250.0, 50.0], false, false);
+        assert_eq!(images, vec!["selected", "normal", "normal", "indicator"]);
+        for (value, expected) in content.iter().zip([0.0, 20.0, 300.0, 80.0].iter()) {
+            assert!((value - expected).abs() < 0.001);
+        }
+
+        // Clicking the header of the third tab.
+        frame(&mut ui_state, &mut state, [250.0, 10.0], true, false);
+        let (_, images) = frame(&mut ui_state, &mut state, [250.0, 10.0], false, true);
+        assert_eq!(state.active, 2);
+        assert_eq!(state.previous.map(|p| p.0), Some(0));
+        // The first header has been drawn before the click was handled.
+        assert_eq!(images, vec!["selected", "normal", "active", "indicator"]);
+
+        let (_, images) = frame(&mut ui_state, &mut state, [250.0, 50.0], false, false);
+        assert_eq!(images, vec!["normal", "normal", "selected", "indicator"]);
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A clickable button made of a 9-patch background with a text over it.
+//!
+//! This is the same as drawing an `image9_button` and a `label::contain` on top of it, except
+//! that all the parameters that usually don't change from one button to another are grouped in a
+//! `Style`.
+
+use Alignment;
+use DrawImage;
+use DrawText;
+use DrawContext;
+use UiState;
+
+use widgets;
+use widgets::Interaction;
+use widgets::Interactions;
+use widgets::Look;
+use widgets::image9;
+use widgets::label;
+
+/// Appearance of a text button in one of its states.
+pub struct StateStyle<'a, I: ?Sized + 'a, T: ?Sized + 'a> {
+    /// 9-patch image of the background.
+    pub image: &'a I,
+    /// Style of the text.
+    pub text_style: &'a T,
+}
+
+impl<'a, I: ?Sized + 'a, T: ?Sized + 'a> Clone for StateStyle<'a, I, T> {
+    #[inline]
+    fn clone(&self) -> StateStyle<'a, I, T> {
+        *self
+    }
+}
+
+impl<'a, I: ?Sized + 'a, T: ?Sized + 'a> Copy for StateStyle<'a, I, T> {}
+
+/// Appearance of a text button.
+pub struct Style<'a, I: ?Sized + 'a, T: ?Sized + 'a> {
+    /// Appearance when the button is neither hovered nor active.
+    pub normal: StateStyle<'a, I, T>,
+    /// Appearance when the cursor hovers the button.
+    pub hovered: StateStyle<'a, I, T>,
+    /// Appearance while the button is being pressed.
+    pub active: StateStyle<'a, I, T>,
+    /// Appearance when the context is disabled. See `DrawContext::disabled`.
+    pub disabled: StateStyle<'a, I, T>,
+
+    /// Width of the left border of the background, in logical pixels. See `image9::draw_pixels`.
+    pub left_border_pixels: f32,
+    /// Percentages of the image that are the top, right, bottom and left borders of the 9-patch.
+    pub image_borders: [f32; 4],
+
+    /// Space between the borders of the button and the text, in logical pixels, in the order top,
+    /// right, bottom, left.
+    pub padding: [f32; 4],
+    /// Alignment of the text within the padded area.
+    pub alignment: Alignment,
+}
+
+impl<'a, I: ?Sized + 'a, T: ?Sized + 'a> Clone for Style<'a, I, T> {
+    #[inline]
+    fn clone(&self) -> Style<'a, I, T> {
+        *self
+    }
+}
+
+impl<'a, I: ?Sized + 'a, T: ?Sized + 'a> Copy for Style<'a, I, T> {}
+
+/// Draws a text button over the whole area of the context.
+pub fn draw<D, I, T>(draw: &DrawContext<D>, ui_state: &mut UiState, style: &Style<I, T>,
+                     text: &str) -> Interaction
+    where D: ?Sized + DrawImage<I> + DrawText<T>, I: ?Sized, T: ?Sized
+{
+    draw_interactions(draw, ui_state, style, text).interaction()
+}
+
+/// Same as `draw`, except that it returns the detailed `Interactions`.
+pub fn draw_interactions<D, I, T>(draw: &DrawContext<D>, ui_state: &mut UiState,
+                                  style: &Style<I, T>, text: &str) -> Interactions
+    where D: ?Sized + DrawImage<I> + DrawText<T>, I: ?Sized, T: ?Sized
+{
+    let (interactions, look) = widgets::button(draw, ui_state);
+
+    let state_style = match look {
+        Look::Normal => &style.normal,
+        Look::Hovered => &style.hovered,
+        Look::Active => &style.active,
+        Look::Disabled => &style.disabled,
+    };
+
+    image9::draw_pixels(draw, style.left_border_pixels, state_style.image, style.image_borders[0],
+                        style.image_borders[1], style.image_borders[2], style.image_borders[3]);
+
+    let text_area = draw.margin_pixels(style.padding[0], style.padding[1], style.padding[2],
+                                       style.padding[3]);
+    label::contain(&text_area, state_style.text_style, text, &style.alignment);
+
+    interactions
+}
+
+#[cfg(test)]
+mod tests {
+    use Alignment;
+    use UiState;
+
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+    use widgets::text_button;
+
+    /// Draws a frame with a text button covering a viewport of 100x100 pixels. Returns whether
+    /// it was clicked, the image of its background, and its text.
+    fn frame(ui_state: &mut UiState, cursor: Option<[f32; 2]>, pressed: bool, released: bool,
+             disabled: bool) -> (bool, String, String)
+    {
+        let state_style = |image| text_button::StateStyle { image, text_style: "text" };
+        let style = text_button::Style {
+            normal: state_style("normal"),
+            hovered: state_style("hovered"),
+            active: state_style("active"),
+            disabled: state_style("disabled"),
+            left_border_pixels: 4.0,
+            image_borders: [0.1, 0.1, 0.1, 0.1],
+            padding: [4.0, 4.0, 4.0, 4.0],
+            alignment: Alignment::center(),
+        };
+
+        let mut drawer = Recorder::default();
+
+        let clicked = {
+            let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, cursor, pressed, released);
+            let ctxt = ctxt.disabled(disabled);
+            text_button::draw(&ctxt, ui_state, &style, "ok").clicked()
+        };
+
+        // The 9-patch background is made of nine images.
+        assert_eq!(drawer.images.len(), 9);
+        (clicked, drawer.images[0].clone(), drawer.text)
+    }
+
+    #[test]
+    fn states() {
+        let mut ui_state = UiState::default();
+        let cursor = Some(cursor_gl([100.0, 100.0], [50.0, 50.0]));
+
+        assert_eq!(frame(&mut ui_state, None, false, false, false),
+                   (false, "normal".to_owned(), "ok".to_owned()));
+        assert_eq!(frame(&mut ui_state, cursor, false, false, false).1, "hovered");
+        assert_eq!(frame(&mut ui_state, cursor, true, false, false).1, "active");
+        assert_eq!(frame(&mut ui_state, cursor, false, true, false),
+                   (true, "active".to_owned(), "ok".to_owned()));
+
+        // A disabled button can't be clicked.
+        frame(&mut ui_state, cursor, true, false, true);
+        assert_eq!(frame(&mut ui_state, cursor, false, true, true),
+                   (false, "disabled".to_owned(), "ok".to_owned()));
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A tooltip is a panel that appears next to the cursor once it has hovered an element for some
+//! time.
+//!
+//! The moment when the cursor started hovering the element is stored in the `UiState` with
+//! `DrawContext::widget_data`.
+//!
+//! The panel is placed at the bottom-right of the cursor, or on the other side if there isn't
+//! enough room in the viewport. It is drawn with `DrawContext::draw_deferred` on the layer above
+//! the one of the context.
+
+use std::borrow::Borrow;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use Alignment;
+use DrawImage;
+use DrawText;
+use DrawContext;
+use UiState;
+
+use widgets::image;
+use widgets::label;
+
+/// Distance in logical pixels between the cursor and the panel.
+const CURSOR_OFFSET: f32 = 16.0;
+
+/// Moment when the cursor started hovering a context that has a tooltip.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct HoverStart(SystemTime);
+
+/// Draws a tooltip with a background image and a line of text if the cursor has been hovering
+/// the context for at least `delay`.
+///
+/// The dimensions of the panel are in logical pixels. The text is drawn with `label::contain`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<'b, D, I, T>(draw: &DrawContext<'b, D>, ui_state: &mut UiState, delay: Duration,
+                         width: f32, height: f32, background: &I, text_style: &T, text: &str)
+    where D: ?Sized + DrawImage<I> + DrawText<T> + 'b, I: ?Sized + ToOwned, I::Owned: 'b,
+          T: ?Sized + ToOwned, T::Owned: 'b
+{
+    let panel = match area(draw, ui_state, delay, width, height) {
+        Some(p) => p,
+        None => return,
+    };
+
+    let background = background.to_owned();
+    let text_style = text_style.to_owned();
+    let text = text.to_owned();
+
+    panel.draw_deferred(move |panel| {
+        image::stretch(panel, background.borrow());
+        label::contain(&panel.uniform_margin(0.1, 0.1, 0.1, 0.1), text_style.borrow(), &text,
+                       &Alignment::center());
+    });
+}
+
+/// If the cursor has been hovering the context for at least `delay`, returns the context where
+/// the panel of the tooltip must be drawn. Otherwise returns `None`.
+///
+/// The dimensions of the panel are in logical pixels. The returned context belongs to the layer
+/// above the one of `draw`, but you have to use `draw_deferred` for the panel to be drawn on top
+/// of the other widgets.
+pub fn area<'b, D: ?Sized>(draw: &DrawContext<'b, D>, ui_state: &mut UiState, delay: Duration,
+                           width: f32, height: f32) -> Option<DrawContext<'b, D>>
+{
+    let widget_id = draw.reserve_widget_id();
+
+    // When the context isn't hovered, the start of the hover isn't accessed and is therefore
+    // dropped at the next frame.
+    if !draw.is_cursor_hovering_uncovered(ui_state) {
+        return None;
+    }
+
+    let now = draw.frame_time();
+    let start = draw.widget_data(ui_state, &widget_id, || HoverStart(now)).0;
+
+    match now.duration_since(start) {
+        Ok(elapsed) if elapsed >= delay => (),
+        _ => return None,
+    };
+
+    let cursor = draw.cursor_position_pixels()?;
+
+    let viewport = draw.viewport();
+    let viewport_width = viewport.width_pixels();
+    let viewport_height = viewport.height_pixels();
+
+    // Placing the panel at the bottom-right of the cursor, or at the top-left if it doesn't fit.
+    let mut x = cursor[0] + CURSOR_OFFSET;
+    if x + width > viewport_width {
+        x = cursor[0] - CURSOR_OFFSET - width;
+    }
+    let x = x.max(0.0).min((viewport_width - width).max(0.0));
+
+    let mut y = cursor[1] + CURSOR_OFFSET;
+    if y + height > viewport_height {
+        y = cursor[1] - CURSOR_OFFSET - height;
+    }
+    let y = y.max(0.0).min((viewport_height - height).max(0.0));
+
+    Some(viewport.absolute_pixels(x, y, width, height, &Alignment::top_left()).input_overlay())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    use Input;
+    use UiState;
+
+    use widgets::tests::cursor_gl;
+    use widgets::tooltip;
+
+    /// Draws a frame where a tooltip of 40x20 pixels is attached to the whole viewport of 100x100
+    /// pixels. `cursor` is in pixels from the top-left hand corner, and `ms` is the time of the
+    /// frame. Returns the position of the panel, if it's shown.
+    fn frame(ui_state: &mut UiState, cursor: Option<[f32; 2]>, ms: u64) -> Option<[f32; 2]> {
+        let mut input = Input::new(cursor.map(|c| cursor_gl([100.0, 100.0], c)));
+        input.time = UNIX_EPOCH + Duration::from_millis(ms);
+        let mut drawer = ();
+        let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+
+        tooltip::area(&ctxt, ui_state, Duration::from_millis(500), 40.0, 20.0).map(|panel| {
+            let rect = panel.rect_pixels();
+            assert!((rect.width - 40.0).abs() < 0.001 && (rect.height - 20.0).abs() < 0.001);
+            [rect.x, rect.y]
+        })
+    }
+
+    fn assert_near(position: Option<[f32; 2]>, expected: [f32; 2]) {
+        let position = position.unwrap();
+        assert!((position[0] - expected[0]).abs() < 0.001, "{:?}", position);
+        assert!((position[1] - expected[1]).abs() < 0.001, "{:?}", position);
+    }
+
+    #[test]
+    fn delay() {
+        let mut ui_state = UiState::default();
+
+        assert_eq!(frame(&mut ui_state, Some([10.0, 10.0]), 1000), None);
+        assert_eq!(frame(&mut ui_state, Some([10.0, 10.0]), 1400), None);
+        assert_near(frame(&mut ui_state, Some([10.0, 10.0]), 1500), [26.0, 26.0]);
+
+        // Leaving the context restarts the delay.
+        assert_eq!(frame(&mut ui_state, None, 1600), None);
+        assert_eq!(frame(&mut ui_state, Some([10.0, 10.0]), 1700), None);
+        assert_near(frame(&mut ui_state, Some([10.0, 10.0]), 2200), [26.0, 26.0]);
+    }
+
+    #[test]
+    fn stays_in_viewport() {
+        let mut ui_state = UiState::default();
+
+        // Near the bottom-right hand corner, the panel goes to the top-left of the cursor.
+        frame(&mut ui_state, Some([90.0, 90.0]), 0);
+        assert_near(frame(&mut ui_state, Some([90.0, 90.0]), 500), [34.0, 54.0]);
+
+        // If it doesn't fit on either side, it's pushed against the border.
+        assert_near(frame(&mut ui_state, Some([50.0, 95.0]), 1000), [0.0, 59.0]);
+    }
+}
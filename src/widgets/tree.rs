@@ -0,0 +1,257 @@
+// Copyright 2016 immi Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A tree view shows hierarchical data, like a scene graph or a file system, one node per row.
+//!
+//! Each node is indented according to its depth. Nodes that have children are preceded with an
+//! arrow that expands or collapses them when clicked. Clicking the rest of a row selects the node.
+//!
+//! Nodes are identified by a `WidgetId` that must stay the same from one frame to another, for
+//! example `WidgetId::from(entity_id)`. The set of expanded nodes and the selected node are
+//! stored by their identifier in a `State` owned by the caller.
+//!
+//! Like lists, trees are virtualized: the content of a row is only drawn if the row is visible.
+//! They also scroll by one row per line scrolled with the mouse wheel while the cursor is over
+//! them.
+
+use std::collections::HashSet;
+
+use Alignment;
+use DrawImage;
+use DrawContext;
+use UiState;
+use WidgetId;
+
+use widgets;
+
+/// A node of a tree.
+pub trait Node: Sized {
+    /// Returns the identifier of the node. Must be the same from one frame to another.
+    fn id(&self) -> WidgetId;
+
+    /// Returns the children of the node.
+    fn children(&self) -> &[Self];
+}
+
+/// State of a tree view.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct State {
+    /// Identifiers of the nodes that are expanded.
+    pub expanded: HashSet<WidgetId>,
+
+    /// Identifier of the node that is selected.
+    pub selection: Option<WidgetId>,
+
+    /// Index of the first visible row.
+    pub scroll: usize,
+}
+
+impl State {
+    /// Returns true if the node with the given identifier is expanded.
+    #[inline]
+    pub fn is_expanded(&self, id: &WidgetId) -> bool {
+        self.expanded.contains(id)
+    }
+
+    /// Expands the node if it's collapsed, and collapses it otherwise.
+    #[inline]
+    pub fn toggle(&mut self, id: &WidgetId) {
+        if !self.expanded.remove(id) {
+            self.expanded.insert(id.clone());
+        }
+    }
+}
+
+/// Draws a tree view over the whole area of the context.
+///
+/// `row_height` is the height of each row and `indentation` the horizontal offset of each level
+/// of depth, both in logical pixels. The `expanded_image` or `collapsed_image` arrow is drawn in
+/// a square at the start of the rows of nodes that have children. The `hovered_image` is drawn
+/// behind the row that is hovered and the `selected_image` behind the row that is selected.
+///
+/// Then `draw_row` is called with the context of the row content, which is at the right of the
+/// arrow, the depth of the node and the node, for each visible row.
+///
+/// Returns `true` if the selection has changed.
+#[allow(clippy::too_many_arguments)]
+pub fn draw<'b, D, I, N, F>(draw: &DrawContext<'b, D>, ui_state: &mut UiState, state: &mut State,
+                            roots: &[N], row_height: f32, indentation: f32, expanded_image: &I,
+                            collapsed_image: &I, hovered_image: &I, selected_image: &I,
+                            mut draw_row: F) -> bool
+    where D: ?Sized + DrawImage<I>, I: ?Sized, N: Node, F: FnMut(&DrawContext<'b, D>, usize, &N)
+{
+    let draw = draw.animation_stop();
+    let widget_id = draw.reserve_widget_id();
+    let previous_selection = state.selection.clone();
+
+    let mut rows = Vec::new();
+    flatten(state, roots, 0, &mut rows);
+
+    let visible_rows = (draw.height_pixels() / row_height).floor().max(1.0) as usize;
+    widgets::scroll_with_wheel(&draw, ui_state, &mut state.scroll);
+    if state.scroll + visible_rows > rows.len() {
+        state.scroll = rows.len().saturating_sub(visible_rows);
+    }
+
+    let hovered = draw.hit_test(ui_state, &widget_id);
+    if hovered && draw.cursor_was_pressed() {
+        ui_state.active_widget = Some(widget_id.clone());
+        ui_state.focused_widget = Some(widget_id.clone());
+    }
+
+    let width = draw.width_pixels();
+
+    let scroll = state.scroll;
+    for (row_num, &(depth, node)) in rows[scroll ..].iter().take(visible_rows).enumerate() {
+        let row = draw.absolute_pixels(0.0, row_num as f32 * row_height, width, row_height,
+                                       &Alignment::top_left());
+        let row_hovered = hovered && row.is_cursor_hovering();
+        let node_id = node.id();
+
+        let offset = depth as f32 * indentation;
+        let arrow = row.absolute_pixels(offset, 0.0, row_height, row_height,
+                                        &Alignment::top_left());
+        let content = row.absolute_pixels(offset + row_height, 0.0,
+                                          (width - offset - row_height).max(0.0), row_height,
+                                          &Alignment::top_left());
+        let has_children = !node.children().is_empty();
+
+        if row_hovered && draw.cursor_was_released() &&
+           ui_state.active_widget == Some(widget_id.clone())
+        {
+            ui_state.active_widget = None;
+
+            if has_children && arrow.is_cursor_hovering() {
+                state.toggle(&node_id);
+            } else {
+                state.selection = Some(node_id.clone());
+            }
+        }
+
+        if state.selection.as_ref() == Some(&node_id) {
+            row.draw().draw_image(selected_image, &row.matrix());
+        } else if row_hovered {
+            row.draw().draw_image(hovered_image, &row.matrix());
+        }
+
+        if has_children {
+            let image = if state.is_expanded(&node_id) { expanded_image } else { collapsed_image };
+            arrow.draw().draw_image(image, &arrow.matrix());
+        }
+
+        draw_row(&content, depth, node);
+    }
+
+    state.selection != previous_selection
+}
+
+/// Appends the nodes that are visible to `out`, in the order they are shown, with their depth.
+fn flatten<'n, N>(state: &State, nodes: &'n [N], depth: usize, out: &mut Vec<(usize, &'n N)>)
+    where N: Node
+{
+    for node in nodes {
+        out.push((depth, node));
+
+        if state.is_expanded(&node.id()) {
+            flatten(state, node.children(), depth + 1, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Input;
+    use UiState;
+    use WidgetId;
+
+    use widgets::tests::Recorder;
+    use widgets::tests::cursor_gl;
+    use widgets::tree;
+
+    struct TestNode(usize, Vec<TestNode>);
+
+    impl tree::Node for TestNode {
+        fn id(&self) -> WidgetId {
+            WidgetId::from(self.0)
+        }
+
+        fn children(&self) -> &[TestNode] {
+            &self.1
+        }
+    }
+
+    /// Draws a frame of the tree `1 { 2, 3 }, 4` in a viewport of 100x100 pixels, with rows of
+    /// 10 pixels. `cursor` is in pixels from the top-left hand corner. Returns whether the
+    /// selection changed, and the depth and identifier of the rows that have been drawn.
+    fn frame(ui_state: &mut UiState, state: &mut tree::State, cursor: [f32; 2], pressed: bool,
+             released: bool) -> (bool, Vec<(usize, usize)>)
+    {
+        let roots = vec![TestNode(1, vec![TestNode(2, vec![]), TestNode(3, vec![])]),
+                         TestNode(4, vec![])];
+
+        let mut drawer = Recorder::default();
+        let cursor = cursor_gl([100.0, 100.0], cursor);
+        let ctxt = ::draw().draw(100.0, 100.0, &mut drawer, Some(cursor), pressed, released);
+
+        let mut rows = Vec::new();
+        let changed = tree::draw(&ctxt, ui_state, state, &roots, 10.0, 10.0, "expanded",
+                                 "collapsed", "hovered", "selected",
+                                 |_, depth, node| rows.push((depth, node.0)));
+        (changed, rows)
+    }
+
+    fn click(ui_state: &mut UiState, state: &mut tree::State, cursor: [f32; 2]) -> bool {
+        frame(ui_state, state, cursor, true, false);
+        frame(ui_state, state, cursor, false, true).0
+    }
+
+    #[test]
+    fn expand_and_select() {
+        let mut ui_state = UiState::default();
+        let mut state = tree::State::default();
+
+        let (_, rows) = frame(&mut ui_state, &mut state, [50.0, 50.0], false, false);
+        assert_eq!(rows, vec![(0, 1), (0, 4)]);
+
+        // Clicking the arrow expands the node without selecting it.
+        assert!(!click(&mut ui_state, &mut state, [5.0, 5.0]));
+        assert_eq!(state.selection, None);
+        let (_, rows) = frame(&mut ui_state, &mut state, [50.0, 50.0], false, false);
+        assert_eq!(rows, vec![(0, 1), (1, 2), (1, 3), (0, 4)]);
+
+        // Clicking the rest of a row selects the node.
+        assert!(click(&mut ui_state, &mut state, [50.0, 25.0]));
+        assert_eq!(state.selection, Some(WidgetId::from(3)));
+        assert!(click(&mut ui_state, &mut state, [50.0, 5.0]));
+        assert_eq!(state.selection, Some(WidgetId::from(1)));
+        assert!(state.is_expanded(&WidgetId::from(1)));
+
+        assert!(!click(&mut ui_state, &mut state, [5.0, 5.0]));
+        let (_, rows) = frame(&mut ui_state, &mut state, [50.0, 50.0], false, false);
+        assert_eq!(rows, vec![(0, 1), (0, 4)]);
+    }
+
+    #[test]
+    fn wheel() {
+        let mut ui_state = UiState::default();
+        let mut state = tree::State::default();
+        let roots: Vec<_> = (0 .. 20).map(|n| TestNode(n, vec![])).collect();
+
+        for &(lines, scroll) in [(4.0, 4), (20.0, 10), (-3.0, 7)].iter() {
+            let mut drawer = Recorder::default();
+            let mut input = Input::new(Some(cursor_gl([100.0, 100.0], [50.0, 50.0])));
+            input.wheel = [0.0, lines];
+            let ctxt = ::draw().draw_with_input(100.0, 100.0, &mut drawer, &input);
+
+            let mut rows = Vec::new();
+            tree::draw(&ctxt, &mut ui_state, &mut state, &roots, 10.0, 10.0, "expanded",
+                       "collapsed", "hovered", "selected", |_, _, node| rows.push(node.0));
+            assert_eq!(state.scroll, scroll);
+            assert_eq!(rows, (scroll .. scroll + 10).collect::<Vec<_>>());
+        }
+    }
+}